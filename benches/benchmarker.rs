@@ -1,12 +1,22 @@
 mod util;
 
-use crate::util::{benchmark_kzg10, benchmark_gwc19, benchmark_djba21};
+use crate::util::{benchmark_kzg10, benchmark_gwc19, benchmark_djba21, benchmark_shplonk, benchmark_pst13};
 use criterion::{Criterion, criterion_group, criterion_main};
 
 use ark_bls12_381::Bls12_381;
 use ark_bls12_377::Bls12_377;
 use ark_bn254::Bn254;
 
+/// Expands `(count, num_samples)` tuples into `(num_poly, num_points, num_samples)`
+/// tuples: first with as many polynomials as points, and then sweeping each of
+/// the two dimensions separately while the other is fixed at 1.
+fn sweep(counts: &[(usize, usize)]) -> Vec<(usize, usize, usize)> {
+    let mut ret: Vec<_> = counts.iter().map(|&(n, s)| (n, n, s)).collect();
+    ret.extend(counts.iter().filter(|&&(n, _)| n > 1).map(|&(n, s)| (n, 1, s)));
+    ret.extend(counts.iter().filter(|&&(n, _)| n > 1).map(|&(n, s)| (1, n, s)));
+    ret
+}
+
 fn benchmark(c: &mut Criterion) {
     let poly_deg = [8, 16, 32, 64, 128, 256];
     // tuples mean (num_poly/num_points, num_samples)
@@ -16,17 +26,28 @@ fn benchmark(c: &mut Criterion) {
         (256, 25), (512, 25), (1024, 25)
     ];
 
-    benchmark_kzg10::<Bls12_381>(c, "bls12381", &poly_deg, &poly_count[0..7]);
-    benchmark_kzg10::<Bls12_377>(c, "bls12377", &poly_deg, &poly_count[0..7]);
-    benchmark_kzg10::<Bn254>(c, "bn254", &poly_deg, &poly_count[0..7]);
+    benchmark_kzg10::<Bls12_381>(c, "bls12381", &poly_deg, &sweep(&poly_count[0..7]));
+    benchmark_kzg10::<Bls12_377>(c, "bls12377", &poly_deg, &sweep(&poly_count[0..7]));
+    benchmark_kzg10::<Bn254>(c, "bn254", &poly_deg, &sweep(&poly_count[0..7]));
 
-    benchmark_gwc19::<Bls12_381>(c, "bls12381", &poly_deg, &poly_count[0..10]);
-    benchmark_gwc19::<Bls12_377>(c, "bls12377", &poly_deg, &poly_count[0..10]);
-    benchmark_gwc19::<Bn254>(c, "bn254", &poly_deg, &poly_count[0..10]);
+    benchmark_gwc19::<Bls12_381>(c, "bls12381", &poly_deg, &sweep(&poly_count[0..10]));
+    benchmark_gwc19::<Bls12_377>(c, "bls12377", &poly_deg, &sweep(&poly_count[0..10]));
+    benchmark_gwc19::<Bn254>(c, "bn254", &poly_deg, &sweep(&poly_count[0..10]));
     
-    benchmark_djba21::<Bls12_381>(c, "bls12381", &poly_deg, &poly_count);
-    benchmark_djba21::<Bls12_377>(c, "bls12377", &poly_deg, &poly_count);
-    benchmark_djba21::<Bn254>(c, "bn254", &poly_deg, &poly_count);
+    benchmark_djba21::<Bls12_381>(c, "bls12381", &poly_deg, &sweep(&poly_count));
+    benchmark_djba21::<Bls12_377>(c, "bls12377", &poly_deg, &sweep(&poly_count));
+    benchmark_djba21::<Bn254>(c, "bn254", &poly_deg, &sweep(&poly_count));
+
+    benchmark_shplonk::<Bls12_381>(c, "bls12381", &poly_deg, &sweep(&poly_count));
+    benchmark_shplonk::<Bls12_377>(c, "bls12377", &poly_deg, &sweep(&poly_count));
+    benchmark_shplonk::<Bn254>(c, "bn254", &poly_deg, &sweep(&poly_count));
+
+    // Polynomials in n variables have 2^n coefficients, as many as those of
+    // the univariate polynomials of degree 2^n above.
+    let num_vars = [3, 4, 5, 6, 7, 8];
+    benchmark_pst13::<Bls12_381>(c, "bls12381", &num_vars, &poly_count[0..7]);
+    benchmark_pst13::<Bls12_377>(c, "bls12377", &num_vars, &poly_count[0..7]);
+    benchmark_pst13::<Bn254>(c, "bn254", &num_vars, &poly_count[0..7]);
 }
 
 criterion_group!(bench, benchmark);
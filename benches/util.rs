@@ -1,29 +1,76 @@
-use kzg_commitments::{poly_commit::PolyCommit, kzg10::KZG10, gwc19::GWC19, djba21::DJBA21};
+#[path = "../tests/util.rs"]
+mod test_util;
 
-use ark_poly::{DenseUVPolynomial, univariate::DensePolynomial};
+use test_util::test_rng;
+use kzg_commitments::{poly_commit::PolyCommit, kzg10::KZG10, gwc19::GWC19, djba21::DJBA21, shplonk::SHPLONK, pst13::PST13};
+
+use ark_poly::{DenseMultilinearExtension, DenseUVPolynomial, MultilinearExtension, univariate::DensePolynomial};
 use ark_ec::pairing::Pairing;
-use ark_std::{rand::Rng, test_rng, cmp::max};
+use ark_std::{rand::{CryptoRng, Rng}, cmp::max};
 use ark_ff::UniformRand;
 
 use criterion::Criterion;
 
-pub fn benchmark_kzg10<E: Pairing>(c: &mut Criterion, curve_name: &str, poly_deg: &[usize], poly_count: &[(usize, usize)]) {
-    benchmark_poly_commit_with_curve::<E, KZG10<E>>(c, "kzg10", curve_name, &|_|(), &mut test_rng(), poly_deg, poly_count);
+pub fn benchmark_kzg10<E: Pairing>(c: &mut Criterion, curve_name: &str, poly_deg: &[usize], counts: &[(usize, usize, usize)]) {
+    benchmark_poly_commit_with_curve::<E, KZG10<E>>(c, "kzg10", curve_name, &|_|(), &mut test_rng(), poly_deg, counts);
 }
 
-pub fn benchmark_gwc19<E: Pairing>(c: &mut Criterion, curve_name: &str, poly_deg: &[usize], poly_count: &[(usize, usize)]) {
+pub fn benchmark_gwc19<E: Pairing>(c: &mut Criterion, curve_name: &str, poly_deg: &[usize], counts: &[(usize, usize, usize)]) {
     fn verifier_init<E: Pairing>(t: usize) -> <GWC19<E> as PolyCommit<E>>::VerifierParams {
         point_generator::<E>(t, &mut test_rng())
     }
-    benchmark_poly_commit_with_curve::<E, GWC19<E>>(c, "gwc19", curve_name, &verifier_init::<E>, &mut test_rng(), poly_deg, poly_count);
+    benchmark_poly_commit_with_curve::<E, GWC19<E>>(c, "gwc19", curve_name, &verifier_init::<E>, &mut test_rng(), poly_deg, counts);
 }
 
-pub fn benchmark_djba21<E: Pairing>(c: &mut Criterion, curve_name: &str, poly_deg: &[usize], poly_count: &[(usize, usize)]) {
+pub fn benchmark_djba21<E: Pairing>(c: &mut Criterion, curve_name: &str, poly_deg: &[usize], counts: &[(usize, usize, usize)]) {
     fn verifier_init<E: Pairing>(_t: usize) -> <DJBA21<E> as PolyCommit<E>>::VerifierParams {
         let v = point_generator::<E>(2, &mut test_rng());
         (v[0], v[1])
     }
-    benchmark_poly_commit_with_curve::<E, DJBA21<E>>(c, "djba21", curve_name, &verifier_init::<E>, &mut test_rng(), poly_deg, poly_count);
+    benchmark_poly_commit_with_curve::<E, DJBA21<E>>(c, "djba21", curve_name, &verifier_init::<E>, &mut test_rng(), poly_deg, counts);
+}
+
+pub fn benchmark_shplonk<E: Pairing>(c: &mut Criterion, curve_name: &str, poly_deg: &[usize], counts: &[(usize, usize, usize)]) {
+    fn verifier_init<E: Pairing>(_t: usize) -> <SHPLONK<E> as PolyCommit<E>>::VerifierParams {
+        let v = point_generator::<E>(2, &mut test_rng());
+        (v[0], v[1])
+    }
+    benchmark_poly_commit_with_curve::<E, SHPLONK<E>>(c, "shplonk", curve_name, &verifier_init::<E>, &mut test_rng(), poly_deg, counts);
+}
+
+/// Benchmarks PST13, which is multilinear and so does not implement
+/// `PolyCommit`, for each number of variables in `num_vars` and each
+/// `(num_poly, num_samples)` in `counts`. Every polynomial is opened at a
+/// single point.
+pub fn benchmark_pst13<E: Pairing>(c: &mut Criterion, curve_name: &str, num_vars: &[usize], counts: &[(usize, usize)]) {
+    let mut rng = test_rng();
+    let mut group = c.benchmark_group(String::from("pst13-") + curve_name);
+
+    for &n in num_vars {
+        let pst = PST13::<E>::new();
+        let (pk, vk) = pst.setup_with_rng(n, &mut rng);
+        let point = point_generator::<E>(n, &mut rng);
+
+        for &(count, samples) in counts {
+            group.sample_size(samples);
+            let poly: Vec<_> = (0..count).map(|_| DenseMultilinearExtension::<E::ScalarField>::rand(n, &mut rng)).collect();
+            let c = pst.commit(&pk, &poly).unwrap();
+            let v = pst.evaluate(&poly, &point).unwrap();
+            let p = pst.open(&pk, &poly, &point).unwrap();
+
+            group.bench_function(format!("COMMIT {} | {}", count, n), |b| {
+                b.iter(|| pst.commit(&pk, &poly).unwrap());
+            });
+
+            group.bench_function(format!("OPEN {} | {}", count, n), |b| {
+                b.iter(|| pst.open(&pk, &poly, &point).unwrap());
+            });
+
+            group.bench_function(format!("VERIFY {} | {}", count, n), |b| {
+                b.iter(|| PST13::verify(&c, &vk, &p, &point, &v).unwrap());
+            });
+        }
+    }
 }
 
 pub fn poly_generator_by_degree<E: Pairing>(
@@ -64,67 +111,65 @@ pub fn benchmark_poly_commit_with_curve<E: Pairing, P: PolyCommit<E>> (
     pairing_name: &str,
     curve_name: &str,
     verifier_init: &dyn Fn(usize) -> P::VerifierParams,
-    mut rng: impl Rng,
+    mut rng: impl Rng + CryptoRng,
     poly_deg: &[usize],
-    poly_count: &[(usize, usize)],
+    counts: &[(usize, usize, usize)],
 ) {    
     let mut max_poly_deg = 0;
     for &v in poly_deg {
         max_poly_deg = max(max_poly_deg, v);
     }
+    let mut max_poly_count = 0;
     let mut max_point_count = 0;
-    for &(v, _) in poly_count {
-        max_point_count = max(max_point_count, v);
+    for &(poly_count, point_count, _) in counts {
+        max_poly_count = max(max_poly_count, poly_count);
+        max_point_count = max(max_point_count, point_count);
     }
-    let max_poly_count = max_point_count;
-    let poly_by_deg = poly_generator_by_degree::<E>(max_poly_count, &poly_deg, &mut rng);
+    let poly_by_deg = poly_generator_by_degree::<E>(max_poly_count, poly_deg, &mut rng);
 
     let points = point_generator::<E>(max_point_count, &mut rng);
 
     let mut group = c.benchmark_group(String::new() + pairing_name + "-" + curve_name);
 
-    let pairs = (0..poly_deg.len()).flat_map(|y| (0..poly_count.len()).map(move |x| (x, y)));
+    let pairs = (0..poly_deg.len()).flat_map(|y| (0..counts.len()).map(move |x| (x, y)));
     
     for (count_index, degree_index) in pairs {
         let mut pc = P::new();
-        let (pk, _sk) = pc.setup(max_poly_deg);
-        group.sample_size(poly_count[count_index].1);
+        let (pk, vk) = pc.setup_with_rng(max_poly_deg, &mut rng);
+        let (count, point_count, samples) = counts[count_index];
+        group.sample_size(samples);
         let deg = poly_deg[degree_index];
-        let count = poly_count[count_index].0;
-        if deg > max_poly_deg || count > max_poly_count {
-            continue;
-        }
         let poly_of_deg = &poly_by_deg[degree_index];
         let poly = &poly_of_deg[0..count];
-        let z = &points[0..count];
-        let ver_params = verifier_init(count);
-        let c = pc.commit(&pk, poly);
-        let v = pc.evaluate(poly, z);
-        let p = pc.open(&pk, poly, z, &v, &ver_params);
+        let z = &points[0..point_count];
+        let ver_params = verifier_init(point_count);
+        let c = pc.commit(&pk, poly).unwrap();
+        let v = pc.evaluate(poly, z).unwrap();
+        let p = pc.open(&pk, poly, z, &v, &ver_params).unwrap();
 
-        let ref_tuple = (&pc, &poly, &z, &ver_params, &pk, &c, &v, &p);
+        let ref_tuple = (&pc, &poly, &z, &ver_params, &pk, &vk, &c, &v, &p);
 
         group.bench_with_input(
-            format!("COMMIT {} | {}", count, deg),
+            format!("COMMIT {}x{} | {}", count, point_count, deg),
             &ref_tuple,
-            |b, (djb,  poly,  _z, _ver_param, pk, _c, _v, _p)| {
-                b.iter(|| djb.commit(&pk, &poly));
+            |b, (djb,  poly,  _z, _ver_param, pk, _vk, _c, _v, _p)| {
+                b.iter(|| djb.commit(pk, poly).unwrap());
             },
         );
 
         group.bench_with_input(
-            format!("OPEN {} | {}", count, deg),
+            format!("OPEN {}x{} | {}", count, point_count, deg),
             &ref_tuple,
-            |b, (djb,  poly,  z, ver_params, pk, _c, v, _p)| {
-                b.iter(|| djb.open(&pk, &poly, &z, &v, &ver_params));
+            |b, (djb,  poly,  z, ver_params, pk, _vk, _c, v, _p)| {
+                b.iter(|| djb.open(pk, poly, z, v, ver_params).unwrap());
             },
         );
 
         group.bench_with_input(
-            format!("VERIFY {} | {}", count, deg),
+            format!("VERIFY {}x{} | {}", count, point_count, deg),
             &ref_tuple,
-            |b, (_djb, _poly, z, ver_params, pk, c, v, p)| {
-                b.iter(|| P::verify(&c, &pk, &p, &z, &v, &ver_params));
+            |b, (_djb, _poly, z, ver_params, _pk, vk, c, v, p)| {
+                b.iter(|| P::verify(c, vk, p, z, v, ver_params).unwrap());
             },
         );
     }
@@ -0,0 +1,155 @@
+use crate::srs::Srs;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_ff::{Field, PrimeField, UniformRand, Zero};
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::ops::Mul;
+
+/// Domain separator for the challenges of the contribution proofs.
+const CEREMONY_DOMAIN: &[u8] = b"kzg-commitments/ceremony/v1";
+
+/// A powers-of-tau ceremony, in which participants take turns rerandomising
+/// the SRS with their own secret `tau`. The secret of the final SRS is the
+/// product of every participant's `tau`, and so it is unknown as long as a
+/// single participant discards theirs.
+///
+/// The ceremony starts from the generators of G_1 and G_2, which corresponds
+/// to the (publicly known) secret 1. The resulting SRS may be used with any of
+/// the schemes in this crate once `verify` has returned true.
+#[derive(Debug, Clone)]
+pub struct Ceremony<E: Pairing> {
+    /// The SRS after all contributions so far.
+    pub srs: Srs<E>,
+    /// The contributions made so far, in order.
+    pub contributions: Vec<Contribution<E>>,
+}
+
+/// Record of a single participant's contribution to the ceremony.
+#[derive(Debug, Clone)]
+pub struct Contribution<E: Pairing> {
+    /// Corresponds to g_1^tau, where `tau` is the secret of this participant.
+    pub tau_g1: E::G1,
+    /// Corresponds to g_1^a after this contribution.
+    pub g1_x: E::G1,
+    /// Corresponds to g_2^a after this contribution.
+    pub g2_x: E::G2,
+    /// Schnorr proof of knowledge of `tau`, as the pair (g_1^k, k + c tau).
+    pub pok: (E::G1, E::ScalarField),
+}
+
+impl<E: Pairing> Ceremony<E> {
+    /// Starts a ceremony for polynomials of degree up to `max_deg`.
+    pub fn new(max_deg: usize) -> Self {
+        let g1 = E::G1Affine::generator();
+        let g2 = E::G2::generator();
+        Self {
+            srs: Srs { g1_vec: vec![g1; max_deg + 1], g2_1: g2, g2_x: g2 },
+            contributions: Vec::new(),
+        }
+    }
+
+    /// Rerandomises the SRS with a secret drawn from `rng`, and records a
+    /// proof of the contribution. The secret is dropped when this function
+    /// returns.
+    pub fn contribute<R: RngCore + CryptoRng>(&mut self, rng: &mut R) {
+        let mut tau = E::ScalarField::rand(rng);
+        while tau.is_zero() {
+            tau = E::ScalarField::rand(rng);
+        }
+
+        // g_1^{a^i} becomes g_1^{(a tau)^i}.
+        let mut accum = E::ScalarField::ONE;
+        let mut g1_vec = Vec::with_capacity(self.srs.g1_vec.len());
+        for g in self.srs.g1_vec.iter() {
+            g1_vec.push(g.mul(accum));
+            accum *= tau;
+        }
+        self.srs.g1_vec = E::G1::normalize_batch(&g1_vec);
+        self.srs.g2_x = self.srs.g2_x.mul(tau);
+
+        let g1 = self.srs.g1_vec[0].into_group();
+        let tau_g1 = g1.mul(tau);
+        let g1_x = self.contributions.last().map_or(g1, |c| c.g1_x).mul(tau);
+
+        let k = E::ScalarField::rand(rng);
+        let r = g1.mul(k);
+        let c = Self::challenge(self.contributions.len(), &tau_g1, &g1_x, &self.srs.g2_x, &r);
+
+        self.contributions.push(Contribution {
+            tau_g1,
+            g1_x,
+            g2_x: self.srs.g2_x,
+            pok: (r, k + c * tau),
+        });
+    }
+
+    /// Verifies the full transcript of the ceremony: that every contribution
+    /// builds on the previous one with a secret known to its participant, and
+    /// that the final SRS is well formed and agrees with the last contribution.
+    /// Returns true if it is valid, and false if it is not. A ceremony without
+    /// any contributions is never valid, as its secret is known.
+    pub fn verify(&self) -> bool {
+        let srs = &self.srs;
+        let g1 = E::G1::generator();
+        if self.contributions.is_empty() || srs.g1_vec.is_empty() || srs.g1_vec[0] != g1.into_affine() || srs.g2_1 != E::G2::generator() {
+            return false;
+        }
+
+        // The ceremony starts from the secret 1.
+        let mut prev_g2_x = srs.g2_1;
+        for (i, contribution) in self.contributions.iter().enumerate() {
+            if contribution.tau_g1.is_zero() {
+                return false;
+            }
+
+            // Proof of knowledge of tau: g_1^s = g_1^k (g_1^tau)^c.
+            let (r, s) = contribution.pok;
+            let c = Self::challenge(i, &contribution.tau_g1, &contribution.g1_x, &contribution.g2_x, &r);
+            if g1.mul(s) != r + contribution.tau_g1.mul(c) {
+                return false;
+            }
+
+            // g_2^a has been raised to tau: e(g_1^tau, g_2^a) = e(g_1, g_2^{a tau}).
+            if E::pairing(contribution.tau_g1, prev_g2_x) != E::pairing(g1, contribution.g2_x) {
+                return false;
+            }
+
+            // g_1^a agrees with g_2^a: e(g_1^a, g_2) = e(g_1, g_2^a).
+            if E::pairing(contribution.g1_x, srs.g2_1) != E::pairing(g1, contribution.g2_x) {
+                return false;
+            }
+
+            prev_g2_x = contribution.g2_x;
+        }
+
+        if prev_g2_x != srs.g2_x {
+            return false;
+        }
+        if let (Some(last), Some(g1_x)) = (self.contributions.last(), srs.g1_vec.get(1)) {
+            if last.g1_x.into_affine() != *g1_x {
+                return false;
+            }
+        }
+
+        srs.is_well_formed()
+    }
+
+    /// Fiat-Shamir challenge for the proof of knowledge of the `index`th
+    /// contribution.
+    fn challenge(index: usize, tau_g1: &E::G1, g1_x: &E::G1, g2_x: &E::G2, r: &E::G1) -> E::ScalarField {
+        let mut bytes = Vec::new();
+        (index as u64).serialize_compressed(&mut bytes).unwrap();
+        tau_g1.serialize_compressed(&mut bytes).unwrap();
+        g1_x.serialize_compressed(&mut bytes).unwrap();
+        g2_x.serialize_compressed(&mut bytes).unwrap();
+        r.serialize_compressed(&mut bytes).unwrap();
+
+        let hash = Sha256::new()
+            .chain_update(CEREMONY_DOMAIN)
+            .chain_update(&bytes)
+            .finalize();
+        E::ScalarField::from_le_bytes_mod_order(&hash)
+    }
+}
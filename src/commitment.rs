@@ -0,0 +1,102 @@
+use crate::poly_commit::{check_len, PolyCommitError};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Commitment to a single polynomial, g^{phi(a)}, as made by every scheme in
+/// this crate. Commitments are additively homomorphic: for polynomials f and
+/// g and scalars a and b,
+///     commit(a f + b g) = a commit(f) + b commit(g),
+/// so commitments to linear combinations of committed polynomials can be
+/// computed from the commitments alone, without committing again.
+///
+/// It is serialized exactly as the element of G_1 it wraps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Commitment<E: Pairing>(pub E::G1);
+
+impl<E: Pairing> Commitment<E> {
+    /// Returns \sum_i coeffs_i commitments_i, the commitment to the same linear
+    /// combination of the committed polynomials, computed as a single
+    /// multi-scalar multiplication. Fails unless there is one coefficient per
+    /// commitment.
+    pub fn linear_combination(coeffs: &[E::ScalarField], commitments: &[Self]) -> Result<Self, PolyCommitError> {
+        check_len(commitments.len(), coeffs.len())?;
+        let bases = E::G1::normalize_batch(&to_group(commitments));
+        Ok(Self(E::G1::msm_unchecked(&bases, coeffs)))
+    }
+}
+
+/// Returns the elements of G_1 wrapped by `commitments`.
+pub(crate) fn to_group<E: Pairing>(commitments: &[Commitment<E>]) -> Vec<E::G1> {
+    commitments.iter().map(|c| c.0).collect()
+}
+
+impl<E: Pairing> Zero for Commitment<E> {
+    /// The commitment to the zero polynomial.
+    fn zero() -> Self {
+        Self(E::G1::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<E: Pairing> Add for Commitment<E> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl<E: Pairing> AddAssign for Commitment<E> {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl<E: Pairing> Sub for Commitment<E> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl<E: Pairing> SubAssign for Commitment<E> {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+    }
+}
+
+impl<E: Pairing> Neg for Commitment<E> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl<E: Pairing> Mul<E::ScalarField> for Commitment<E> {
+    type Output = Self;
+
+    fn mul(self, scalar: E::ScalarField) -> Self {
+        Self(self.0 * scalar)
+    }
+}
+
+impl<E: Pairing> MulAssign<E::ScalarField> for Commitment<E> {
+    fn mul_assign(&mut self, scalar: E::ScalarField) {
+        self.0 *= scalar;
+    }
+}
+
+impl<E: Pairing> Sum for Commitment<E> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|c| c.0).sum())
+    }
+}
@@ -1,42 +1,37 @@
-use crate::poly_commit::{PolyCommit};
+use crate::commitment::Commitment;
+use crate::poly_commit::{check_degrees, check_distinct, check_len, check_non_empty, complements, NonInteractive, PolyCommit, PolyCommitError};
+use crate::transcript::Transcript;
+use crate::srs::{Srs, VerifierKey};
 use ark_ff::{UniformRand, Field};
 use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
-use ark_std::test_rng;
+use ark_std::{cfg_iter, rand::{CryptoRng, RngCore}};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use ark_ec::pairing::{Pairing};
 use ark_ec::AdditiveGroup;
 use std::ops::Mul;
 use std::ops::Neg;
 use std::marker::PhantomData;
 
-use crate::utils::poly::{eval_poly_over_g1, lagrange_interpolate};
+use crate::utils::poly::{eval_poly_over_g1, lagrange_interpolate, vanishing_poly};
+
+/// Domain separator for the transcripts of `NonInteractive` proofs.
+const DJBA21_DOMAIN: &[u8] = b"kzg-commitments/djba21/v1";
 
 /// Struct for implementing the batched polynomial commitment scheme
 /// described in [this paper](https://eprint.iacr.org/2020/081.pdf).
 #[derive(Debug)]
 pub struct DJBA21<E: Pairing> {
-    max_deg: usize,
     _phantom: PhantomData<E>,
 }
 
-/// Struct which holds the PK information for DJBA21.
-#[derive(Debug,Clone)]
-pub struct DJBA21_PK<E: Pairing> {
-    /// Corresponds to <g_1, g_1^a, g_1^{a^2}, .... , g_1^{a^t}>
-    pub g1: Vec<E::G1>,
-    /// Corresponds to g_2
-    pub g2_one: E::G2,
-    /// Corresponds to g_2^a
-    pub g2_x: E::G2,
-}
-
-
 /// Implementation of batched polynomial commitments for DJBA21.
 impl<E: Pairing> PolyCommit<E> for DJBA21<E> {
-    type PK = DJBA21_PK<E>;
-    type SK = E::ScalarField;
+    type PK = Srs<E>;
+    type VK = VerifierKey<E>;
     /// The commitment consists of 1 element of G_1 for each polynomial
     /// committed to.
-    type Commitment = Vec<E::G1>;
+    type Commitment = Vec<Commitment<E>>;
     /// As in the paper we encode the evaluation of a polynomial `f` at `t`
     /// points as a polynomial with degree `t` which agrees with `f` for
     /// all the `t` points.
@@ -50,138 +45,200 @@ impl<E: Pairing> PolyCommit<E> for DJBA21<E> {
 
     fn new() -> Self {
         Self {
-            max_deg: 0,
             _phantom: PhantomData,
         }
     }
 
     /// Initialises the public key parameters by as described in the paper,
     /// for polynomials of degree up to `max_deg`.
-    fn setup(&mut self, max_deg: usize) -> (Self::PK, Self::SK) {
-        let sk = E::ScalarField::rand(&mut test_rng());
-        self.max_deg = max_deg;
-        
-        let g = E::G1::rand(&mut test_rng());
-
-        let mut pk = Self::PK {
-            g1: Vec::new(),
-            g2_one: E::G2::ZERO,
-            g2_x: E::G2::ZERO,
-        };
-        for i in 0..(max_deg + 1) {
-            //g_1^{sk^i}
-            pk.g1.push(g.mul(sk.pow(&[i as u64])));
-        }
-
-        pk.g2_one = E::G2::rand(&mut test_rng());
-        pk.g2_x = pk.g2_one.mul(sk);
+    fn setup_with_rng<R: RngCore + CryptoRng>(&mut self, max_deg: usize, rng: &mut R) -> (Self::PK, Self::VK) {
+        let pk = Srs::generate(max_deg, rng);
+        let vk = pk.verifier_key();
+        (pk, vk)
+    }
 
-        (pk, sk)
+    /// Derives the keys for polynomials of degree up to `max_deg` from `srs`.
+    fn trim(&mut self, srs: &Srs<E>, max_deg: usize) -> Result<(Self::PK, Self::VK), PolyCommitError> {
+        srs.trim(max_deg)
     }
 
     /// Commits to the polynomials in `poly`, yields one element of G_1 per
     /// polynomial.
-    fn commit(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>]) -> Self::Commitment {
-        let mut ret = Vec::with_capacity(poly.len());
-
-        for p in poly {
-            ret.push(eval_poly_over_g1::<E>(p, &pk.g1));
-        }
+    fn commit(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>]) -> Result<Self::Commitment, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_degrees(poly, pk.g1_vec.len())?;
 
-        ret
+        Ok(cfg_iter!(poly).map(|p| Commitment(eval_poly_over_g1::<E>(p, &pk.g1_vec))).collect())
     }
 
     /// Evaluates the polynomials in `poly` at each of the points in `z`. For each
     /// polynomial `f` in `poly`, the evaluations are then returned as a polynomial
-    /// which agrees with `f` on all the points in `z`.
-    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Vec<Self::Evaluation> {
-        let mut ret = Vec::new();
-        let mut points = Vec::new();
+    /// which agrees with `f` on all the points in `z`, so the points must be
+    /// distinct.
+    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Result<Vec<Self::Evaluation>, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
+        check_distinct(z)?;
+
+        cfg_iter!(poly).map(|p| {
+            let points: Vec<_> = z.iter().map(|point| (*point, p.evaluate(point))).collect();
+            lagrange_interpolate::<E>(points.as_slice())
+        }).collect()
+    }
 
-        for p in poly {
-            for point in z {
-                points.push((*point, p.evaluate(point)));
-            }
+    /// Creates witnesses to the values of the polynomials in `poly` at all
+    /// the points in `z`. This is the special case of `open_subsets` where
+    /// every polynomial is opened at every point, so each
+    ///     Z_{T\S_i} = Z_{{}} = 1.
+    ///
+    /// It is a logic error for the values in `v` to be incorrect.
+    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<Self::Proof, PolyCommitError> {
+        let poly: Vec<_> = poly.iter().collect();
+        self.open_sets(pk, &poly, &vec![z; poly.len()], v, ver_params)
+    }
 
-            ret.push(lagrange_interpolate::<E>(points.as_slice()));
-            points.clear();
+    /// Verifies that the proof `p` is valid for the given parameters, and returns true
+    /// if it is, and false if it is not. As with `open`, this is the special case of
+    /// `verify_subsets` where every polynomial is opened at every point.
+    fn verify(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<bool, PolyCommitError> {
+        Self::verify_sets(c, vk, p, &vec![z; c.len()], v, ver_params)
+    }
+}
+
+/// As in the paper, `gamma` is squeezed from a transcript over the verifier
+/// key, commitments, points and evaluations, and then `z` only once `W` has
+/// been absorbed.
+impl<E: Pairing> NonInteractive<E> for DJBA21<E> {
+    fn open_non_interactive(&self, pk: &Self::PK, c: &Self::Commitment, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<Self::Proof, PolyCommitError> {
+        let mut transcript = Self::transcript(&pk.try_verifier_key()?, c, z, v);
+        let gamma = transcript.challenge(b"gamma");
+        let poly: Vec<_> = poly.iter().collect();
+        self.open_sets_with(pk, &poly, &vec![z; poly.len()], v, gamma, |W| {
+            transcript.append(b"W", W);
+            transcript.challenge(b"z")
+        })
+    }
+
+    fn verify_non_interactive(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<bool, PolyCommitError> {
+        let mut transcript = Self::transcript(vk, c, z, v);
+        let gamma = transcript.challenge(b"gamma");
+        transcript.append(b"W", &p.0);
+        let ver_params = (gamma, transcript.challenge(b"z"));
+        Self::verify(c, vk, p, z, v, &ver_params)
+    }
+}
+
+impl<E: Pairing> DJBA21<E> {
+    /// Starts the Fiat-Shamir transcript shared by the prover and verifier.
+    fn transcript(vk: &VerifierKey<E>, c: &[Commitment<E>], z: &[E::ScalarField], v: &[DensePolynomial<E::ScalarField>]) -> Transcript {
+        let mut transcript = Transcript::new(DJBA21_DOMAIN);
+        transcript.append(b"g1", &vk.g1);
+        transcript.append(b"g2_1", &vk.g2_1);
+        transcript.append(b"g2_x", &vk.g2_x);
+        transcript.append(b"commitments", c);
+        transcript.append(b"points", z);
+        transcript.append(b"evaluations", v);
+        transcript
+    }
+
+    /// Evaluates each polynomial `f_i` on its own set of points `S_i`, given as
+    /// `(f_i, S_i)` pairs in `queries`. As in `evaluate`, each result is the
+    /// polynomial `r_i` which agrees with `f_i` on `S_i`.
+    pub fn evaluate_subsets(&self, queries: &[(DensePolynomial<E::ScalarField>, Vec<E::ScalarField>)]) -> Result<Vec<DensePolynomial<E::ScalarField>>, PolyCommitError> {
+        check_non_empty(queries)?;
+        for (_, s) in queries {
+            check_non_empty(s)?;
+            check_distinct(s)?;
         }
-        ret
+
+        cfg_iter!(queries).map(|(p, s)| {
+            let points: Vec<_> = s.iter().map(|point| (*point, p.evaluate(point))).collect();
+            lagrange_interpolate::<E>(points.as_slice())
+        }).collect()
+    }
+
+    /// Creates a witness to the values of each polynomial `f_i` on its own set
+    /// of points `S_i`, given as `(f_i, S_i)` pairs in `queries`, where `v[i]`
+    /// is as returned by `evaluate_subsets`. The proof is two elements of G_1
+    /// regardless of the number of polynomials and points.
+    ///
+    /// It is a logic error for the values in `v` to be incorrect.
+    pub fn open_subsets(&self, pk: &Srs<E>, queries: &[(DensePolynomial<E::ScalarField>, Vec<E::ScalarField>)], v: &[DensePolynomial<E::ScalarField>], ver_params: &(E::ScalarField, E::ScalarField)) -> Result<(E::G1, E::G1), PolyCommitError> {
+        let poly: Vec<_> = queries.iter().map(|(p, _)| p).collect();
+        let sets: Vec<_> = queries.iter().map(|(_, s)| s.as_slice()).collect();
+        self.open_sets(pk, &poly, &sets, v, ver_params)
+    }
+
+    /// Verifies the proof `p` created by `open_subsets`, where `c[i]` is the
+    /// commitment to the polynomial opened on the points in `sets[i]`.
+    pub fn verify_subsets(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &(E::G1, E::G1), sets: &[Vec<E::ScalarField>], v: &[DensePolynomial<E::ScalarField>], ver_params: &(E::ScalarField, E::ScalarField)) -> Result<bool, PolyCommitError> {
+        let sets: Vec<_> = sets.iter().map(|s| s.as_slice()).collect();
+        Self::verify_sets(c, vk, p, &sets, v, ver_params)
     }
 
-    /// Creates witnesses to the values of the polynomials in `poly` at all
-    /// the points in `z`.
     /// The calculations carried out below are faithful to the equations
     /// presented in section 4.1 of the paper, and it is recommended this
-    /// section be read in consultation with the paper. We note that since
-    /// each polynomial is being evaluated at all of the points,
-    ///     Z_{T\S_i} = Z_{{}} = 1.
-    ///
-    /// It is a logic error for:
-    ///  - `poly.len() != z.len()`
-    ///  - the values in `v` to be incorrect.
-    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Self::Proof {
+    /// section be read in consultation with the paper. The verifier's
+    /// parameters are `(gamma, z)` in the notation of the paper.
+    fn open_sets(&self, pk: &Srs<E>, poly: &[&DensePolynomial<E::ScalarField>], sets: &[&[E::ScalarField]], v: &[DensePolynomial<E::ScalarField>], ver_params: &(E::ScalarField, E::ScalarField)) -> Result<(E::G1, E::G1), PolyCommitError> {
+        self.open_sets_with(pk, poly, sets, v, ver_params.0, |_| ver_params.1)
+    }
+
+    /// As `open_sets`, but `z` is only chosen by `choose_z` once it has seen
+    /// `W`, which is how the protocol runs in the paper.
+    fn open_sets_with(&self, pk: &Srs<E>, poly: &[&DensePolynomial<E::ScalarField>], sets: &[&[E::ScalarField]], v: &[DensePolynomial<E::ScalarField>], gamma: E::ScalarField, choose_z: impl FnOnce(&E::G1) -> E::ScalarField) -> Result<(E::G1, E::G1), PolyCommitError> {
+        check_non_empty(poly)?;
+        check_len(poly.len(), sets.len())?;
+        let (T, rest) = complements(sets)?;
+        check_len(poly.len(), v.len())?;
+        for p in poly {
+            check_degrees(std::slice::from_ref(*p), pk.g1_vec.len())?;
+        }
+
         //Corresponds to `f` from the paper.
-        let mut f = vec![E::ScalarField::ZERO; poly[0].degree()+1];
-        //Corresponds to `L` from the paper.
-        let mut L = vec![E::ScalarField::ZERO; poly[0].degree()+1];
+        let mut f = vec![];
 
-        //Retains the value of \lambda^{i-1} in the loop, to minimise multiplications.
+        //Retains the value of \gamma^{i-1} in the loop, to minimise multiplications.
         let mut accum = E::ScalarField::ONE;
-        for i in 0..z.len() {
-            // f = f + poly[i].clone() * accum;
-            for (j, c) in poly[i].coeffs().iter().enumerate() {
+        for i in 0..poly.len() {
+            // f = f + Z_{T\S_i} * (poly[i] - v[i]) * accum;
+            let diff = poly[i] - &v[i];
+            let term = if rest[i].is_empty() { diff } else { &vanishing_poly::<E>(&rest[i]) * &diff };
+            if f.len() < term.coeffs().len() {
+                f.resize(term.coeffs().len(), E::ScalarField::ZERO);
+            }
+            for (j, c) in term.coeffs().iter().enumerate() {
                 f[j] += *c * accum;
             }
 
-            // f = f - v[i].clone() * accum;
-            for (j, t) in v[i].coeffs().iter().enumerate() {
-                f[j] -= *t * accum;
-            }
+            accum *= gamma;
+        }
 
-            // L = L + poly[i].clone() * accum;
-            for (j, c) in poly[i].coeffs().iter().enumerate() {
-                L[j] += *c * accum;
-            }
-            L[0] -= v[i].evaluate(&ver_params.1) * accum;
+        // Corresponds to Z_T from the paper.
+        let zt = vanishing_poly::<E>(&T);
 
-            accum *= ver_params.0;
-        }
+        let mut w_partial = DensePolynomial::from_coefficients_vec(f) / zt.clone();
 
-        // ztVec is an auxiliary data structure used to calculate
-        //      Z_T := \prod_{t \in T} (X - t)
-        // This definition can be found in the paper. In this function `z` is `T`.
-        //
-        // The straightforward means of evaluation (multiplying all (X-t) together)
-        // incurs a runtime cost of O(n^2 log(n)). It is better to evaluate it
-        // in the following fashion:
-        //
-        // (X - t_1)    (X - t_2)    (X - t_3)    (X - t_4)
-        // (X - t_1)(X - t_2)    (X - t_3)(X - t_4)
-        // (X - t_1)(X - t_2)(X - t_3)(X - t_4)
-        //
-        // This is to say, we put all of the linear polynomials on the base level,
-        // and then we combine neighbours together until we are left with one element.
-        // This gives a runtime of O(n log(n)) per level, yielding the much better
-        // O(n log^2(n)) complexity for calculation overall.
-        let mut ztVec: Vec<DensePolynomial<E::ScalarField>> = z.iter().map(|v| DensePolynomial::from_coefficients_slice(&[v.neg(), E::ScalarField::ONE])).collect();
-        let mut accum = 2;
-        for i in 0..z.len().ilog2() {
-            for j in 0..(z.len()/accum) {
-                ztVec[j] = ztVec[2*j].clone() * ztVec[2*j+1].clone();
-            }
-            accum *= 2;
-        }
+        let W = eval_poly_over_g1::<E>(&w_partial, &pk.g1_vec);
 
-        // zt is defined as above.
-        let zt = ztVec[0].clone();
+        let z = choose_z(&W);
 
+        //Corresponds to `L` from the paper.
+        let mut L = vec![E::ScalarField::ZERO; poly.iter().map(|p| p.coeffs().len()).max().unwrap_or(0).max(1)];
 
-        let mut w_partial = DensePolynomial::from_coefficients_vec(f) / zt.clone();
+        let mut accum = E::ScalarField::ONE;
+        for i in 0..poly.len() {
+            // L = L + Z_{T\S_i}(z) * (poly[i] - v[i](z)) * accum;
+            let scale = accum * rest[i].iter().map(|t| z - t).product::<E::ScalarField>();
+            for (j, c) in poly[i].coeffs().iter().enumerate() {
+                L[j] += *c * scale;
+            }
+            L[0] -= v[i].evaluate(&z) * scale;
 
-        let W = eval_poly_over_g1::<E>(&w_partial, &pk.g1);
+            accum *= gamma;
+        }
 
-        w_partial = w_partial * zt.evaluate(&ver_params.1);
+        w_partial = w_partial * zt.evaluate(&z);
 
         for (j, c) in w_partial.coeffs().iter().enumerate() {
             L[j] -= c;
@@ -189,56 +246,49 @@ impl<E: Pairing> PolyCommit<E> for DJBA21<E> {
 
         let mut L = DensePolynomial::from_coefficients_vec(L);
 
-        L = L / DensePolynomial::from_coefficients_slice(&[ver_params.1.neg(), E::ScalarField::ONE]);
+        L = L / DensePolynomial::from_coefficients_slice(&[z.neg(), E::ScalarField::ONE]);
 
-        let Wp = eval_poly_over_g1::<E>(&L, &pk.g1);
+        let Wp = eval_poly_over_g1::<E>(&L, &pk.g1_vec);
         
-        (W, Wp)
+        Ok((W, Wp))
     }
 
-    /// Verifies that the proof `p` is valid for the given parameters, and returns true
-    /// if it is, and false if it is not.
-    ///
-    /// The calculations carried out below are faithful to the equations
-    /// presented in section 4.1 of the paper, and it is recommended this
-    /// section be read in consultation with the paper. We note that since
-    fn verify(c: &Self::Commitment, pk: &Self::PK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> bool {
+    /// See `open_sets` for an explanation of the calculation below. Only the
+    /// values of the vanishing polynomials at `z` are needed here, so rather
+    /// than building them we take the products of (z - t) directly.
+    fn verify_sets(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &(E::G1, E::G1), sets: &[&[E::ScalarField]], v: &[DensePolynomial<E::ScalarField>], ver_params: &(E::ScalarField, E::ScalarField)) -> Result<bool, PolyCommitError> {
+        vk.check()?;
+        check_non_empty(c)?;
+        check_len(c.len(), sets.len())?;
+        let (T, rest) = complements(sets)?;
+        check_len(c.len(), v.len())?;
+
         let (W, Wp) = *p;
 
         let mut F = E::G1::ZERO;
 
         let mut mid = E::ScalarField::ZERO;
 
-        //Retains the value of \lambda^{i-1} in the loop, to minimise multiplications.
+        //Retains the value of \gamma^{i-1} in the loop, to minimise multiplications.
         let mut accum = E::ScalarField::ONE;
         for (i, comm) in c.iter().enumerate() {
-            F += comm.mul(accum);
-            mid += accum * v[i].evaluate(&ver_params.1);
+            let scale = accum * rest[i].iter().map(|t| ver_params.1 - t).product::<E::ScalarField>();
+            F += comm.0.mul(scale);
+            mid += scale * v[i].evaluate(&ver_params.1);
 
             accum *= ver_params.0;
         }
 
-        F = F - pk.g1[0].mul(mid);
-
-        // See `open` for an explanation of the calculation below.
-        let mut ztVec: Vec<DensePolynomial<E::ScalarField>> = z.iter().map(|v| DensePolynomial::from_coefficients_slice(&[v.neg(), E::ScalarField::ONE])).collect();
-        let mut accum = 2;
-        for i in 0..z.len().ilog2() {
-            for j in 0..(z.len()/accum) {
-                ztVec[j] = ztVec[2*j].clone() * ztVec[2*j+1].clone();
-            }
-            accum *= 2;
-        }
+        F = F - vk.g1.mul(mid);
 
-        // zt is defined as above.
-        let zt = ztVec[0].clone();
+        let zt_z: E::ScalarField = T.iter().map(|t| ver_params.1 - t).product();
 
-        F -= W.mul(zt.evaluate(&ver_params.1));
+        F -= W.mul(zt_z);
 
 
-        let lhs = E::pairing(F + Wp.mul(ver_params.1), pk.g2_one);
-        let rhs = E::pairing(Wp, pk.g2_x);
+        let lhs = E::pairing(F + Wp.mul(ver_params.1), vk.g2_1);
+        let rhs = E::pairing(Wp, vk.g2_x);
 
-        lhs == rhs
+        Ok(lhs == rhs)
     }
 }
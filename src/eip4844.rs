@@ -0,0 +1,264 @@
+//! The polynomial commitment functions of EIP-4844 (proto-danksharding), as
+//! specified in the Ethereum consensus specs (`polynomial-commitments.md`),
+//! built on `KZG10` over BLS12-381 with the Ethereum KZG ceremony's trusted
+//! setup.
+//!
+//! Blobs, field elements and points are taken and returned in the byte
+//! encodings of the specs, so the results can be compared byte for byte with
+//! other implementations. Where the specs raise an exception, these functions
+//! return an error.
+
+use crate::commitment::Commitment;
+use crate::kzg10::KZG10;
+use crate::poly_commit::{check_len, PolyCommit, PolyCommitError};
+use crate::srs::{LagrangeSrs, VerifierKey};
+use crate::trusted_setup::TrustedSetup;
+use crate::utils::poly::bit_reverse_permutation;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, PrimeField};
+use ark_poly::{EvaluationDomain, Evaluations, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// The number of field elements in a blob.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+/// The length of the big-endian encoding of a field element.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+/// The length of a blob.
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+/// The length of the compressed encoding of a commitment or proof.
+pub const BYTES_PER_COMMITMENT: usize = 48;
+
+/// Domain separator for the challenge of `compute_blob_kzg_proof`.
+const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8] = b"FSBLOBVERIFY_V1_";
+/// Domain separator for the scalars of `verify_blob_kzg_proof_batch`.
+const RANDOM_CHALLENGE_KZG_BATCH_DOMAIN: &[u8] = b"RCKZGBATCH___V1_";
+
+/// The big-endian encoding of a field element.
+pub type Bytes32 = [u8; 32];
+/// The compressed encoding of a point of G_1: a commitment or a proof.
+pub type Bytes48 = [u8; 48];
+
+/// Errors which can occur with the EIP-4844 functions, where the specs would
+/// raise an exception.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Eip4844Error {
+    /// A blob is not `BYTES_PER_BLOB` bytes long.
+    InvalidBlobLength(usize),
+    /// 32 bytes are not the canonical big-endian encoding of a field element,
+    /// that is, they encode a value no less than the field's modulus.
+    NonCanonicalFieldElement,
+    /// 48 bytes are not the compressed encoding of the identity or a point in
+    /// the prime-order subgroup of G_1.
+    InvalidPoint,
+    /// The trusted setup does not hold `FIELD_ELEMENTS_PER_BLOB` G_1 points.
+    InvalidSetup,
+    /// An error from the underlying commitment scheme, such as a mismatch in
+    /// the numbers of blobs, commitments and proofs.
+    PolyCommit(PolyCommitError),
+}
+
+impl fmt::Display for Eip4844Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBlobLength(len) => write!(f, "blob is {len} bytes long, expected {BYTES_PER_BLOB}"),
+            Self::NonCanonicalFieldElement => write!(f, "field element is not canonically encoded"),
+            Self::InvalidPoint => write!(f, "invalid encoding of a point of G1"),
+            Self::InvalidSetup => write!(f, "trusted setup must have {FIELD_ELEMENTS_PER_BLOB} G1 points"),
+            Self::PolyCommit(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Eip4844Error {}
+
+impl From<PolyCommitError> for Eip4844Error {
+    fn from(e: PolyCommitError) -> Self {
+        Self::PolyCommit(e)
+    }
+}
+
+/// The keys and evaluation domain with which the EIP-4844 functions are
+/// computed.
+pub struct KzgSettings {
+    kzg: KZG10<Bls12_381>,
+    pk: LagrangeSrs<Bls12_381>,
+    vk: VerifierKey<Bls12_381>,
+}
+
+impl KzgSettings {
+    /// Derives the settings from the Ethereum KZG ceremony's trusted setup
+    /// (see `TrustedSetup::load`). Fails unless the setup holds exactly
+    /// `FIELD_ELEMENTS_PER_BLOB` G_1 points. Blobs are committed to and opened
+    /// with the setup's Lagrange basis, so they are never interpolated.
+    pub fn new(setup: &TrustedSetup) -> Result<Self, Eip4844Error> {
+        if setup.g1_monomial.len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(Eip4844Error::InvalidSetup);
+        }
+        let mut kzg = KZG10::new();
+        let (_, vk) = kzg.trim(&setup.srs(), FIELD_ELEMENTS_PER_BLOB - 1)?;
+        let domain = Radix2EvaluationDomain::new(FIELD_ELEMENTS_PER_BLOB).ok_or(Eip4844Error::InvalidSetup)?;
+        // The setup lists the Lagrange basis in bit-reversed order, as in the specs.
+        let mut lagrange_vec = setup.g1_lagrange.clone();
+        bit_reverse_permutation(&mut lagrange_vec);
+        let pk = LagrangeSrs { lagrange_vec, domain };
+        Ok(Self { kzg, pk, vk })
+    }
+
+    /// Returns the commitment to the polynomial whose evaluations are `blob`.
+    pub fn blob_to_kzg_commitment(&self, blob: &[u8]) -> Result<Bytes48, Eip4844Error> {
+        let f = self.blob_to_evaluations(blob)?;
+        Ok(g1_to_bytes(&self.kzg.commit_evaluations(&self.pk, &[f])?[0].0))
+    }
+
+    /// Returns the proof of the value `y` of the polynomial whose evaluations
+    /// are `blob` at the point `z`, along with `y`.
+    pub fn compute_kzg_proof(&self, blob: &[u8], z: &Bytes32) -> Result<(Bytes48, Bytes32), Eip4844Error> {
+        let f = self.blob_to_evaluations(blob)?;
+        let z = fr_from_bytes(z)?;
+        let (proof, y) = self.open(f, z)?;
+        Ok((g1_to_bytes(&proof), fr_to_bytes(&y)))
+    }
+
+    /// Returns the proof of the value of the polynomial whose evaluations are
+    /// `blob` at the challenge derived from `blob` and its `commitment`.
+    pub fn compute_blob_kzg_proof(&self, blob: &[u8], commitment: &Bytes48) -> Result<Bytes48, Eip4844Error> {
+        let f = self.blob_to_evaluations(blob)?;
+        g1_from_bytes(commitment)?;
+        let z = compute_challenge(blob, commitment);
+        let (proof, _) = self.open(f, z)?;
+        Ok(g1_to_bytes(&proof))
+    }
+
+    /// Verifies that `proof` shows the polynomial committed to by `commitment`
+    /// takes the value `y` at `z`.
+    pub fn verify_kzg_proof(&self, commitment: &Bytes48, z: &Bytes32, y: &Bytes32, proof: &Bytes48) -> Result<bool, Eip4844Error> {
+        let c = g1_from_bytes(commitment)?;
+        let z = fr_from_bytes(z)?;
+        let y = fr_from_bytes(y)?;
+        let proof = g1_from_bytes(proof)?;
+        Ok(KZG10::verify(&vec![Commitment(c)], &self.vk, &vec![vec![proof]], &[z], &[vec![y]], &())?)
+    }
+
+    /// Verifies a proof created by `compute_blob_kzg_proof`.
+    pub fn verify_blob_kzg_proof(&self, blob: &[u8], commitment: &Bytes48, proof: &Bytes48) -> Result<bool, Eip4844Error> {
+        let f = self.blob_to_evaluations(blob)?;
+        let c = g1_from_bytes(commitment)?;
+        let proof = g1_from_bytes(proof)?;
+        let z = compute_challenge(blob, commitment);
+        let y = self.kzg.evaluate_evaluations(&[f], &[z])?[0][0];
+        Ok(KZG10::verify(&vec![Commitment(c)], &self.vk, &vec![vec![proof]], &[z], &[vec![y]], &())?)
+    }
+
+    /// Verifies proofs created by `compute_blob_kzg_proof` for each of `blobs`
+    /// and the corresponding `commitments` at once. An empty batch is valid.
+    ///
+    /// As in the specs, the checks are combined with powers of a scalar `r`
+    /// derived from all of the inputs into
+    ///     e(\sum r^i (c_i - y_i g + z_i p_i), g2) = e(\sum r^i p_i, g2^a).
+    pub fn verify_blob_kzg_proof_batch<B: AsRef<[u8]>>(&self, blobs: &[B], commitments: &[Bytes48], proofs: &[Bytes48]) -> Result<bool, Eip4844Error> {
+        check_len(blobs.len(), commitments.len())?;
+        check_len(blobs.len(), proofs.len())?;
+        if blobs.is_empty() {
+            return Ok(true);
+        }
+
+        let mut c = Vec::with_capacity(blobs.len());
+        let mut p = Vec::with_capacity(blobs.len());
+        let mut z = Vec::with_capacity(blobs.len());
+        let mut y = Vec::with_capacity(blobs.len());
+        for ((blob, commitment), proof) in blobs.iter().zip(commitments).zip(proofs) {
+            let f = self.blob_to_evaluations(blob.as_ref())?;
+            c.push(g1_from_bytes(commitment)?);
+            p.push(g1_from_bytes(proof)?);
+            let z_i = compute_challenge(blob.as_ref(), commitment);
+            y.push(self.kzg.evaluate_evaluations(&[f], &[z_i])?[0][0]);
+            z.push(z_i);
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(RANDOM_CHALLENGE_KZG_BATCH_DOMAIN);
+        hasher.update((FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+        hasher.update((blobs.len() as u64).to_be_bytes());
+        for i in 0..blobs.len() {
+            hasher.update(commitments[i]);
+            hasher.update(fr_to_bytes(&z[i]));
+            hasher.update(fr_to_bytes(&y[i]));
+            hasher.update(proofs[i]);
+        }
+        let r = Fr::from_be_bytes_mod_order(&hasher.finalize());
+        let r_powers: Vec<Fr> = std::iter::successors(Some(Fr::ONE), |x| Some(*x * r)).take(blobs.len()).collect();
+
+        // The scalars multiplying each c_i, p_i and g in the left hand side.
+        let p_scalars: Vec<Fr> = r_powers.iter().zip(&z).map(|(r_i, z_i)| *r_i * z_i).collect();
+        let g_scalar: Fr = r_powers.iter().zip(&y).map(|(r_i, y_i)| *r_i * y_i).sum();
+
+        let c = G1Projective::normalize_batch(&c);
+        let p = G1Projective::normalize_batch(&p);
+        let lhs = G1Projective::msm_unchecked(&c, &r_powers) + G1Projective::msm_unchecked(&p, &p_scalars) - self.vk.g1 * g_scalar;
+        let rhs = G1Projective::msm_unchecked(&p, &r_powers);
+
+        Ok(Bls12_381::multi_pairing([lhs, -rhs], [self.vk.g2_1, self.vk.g2_x]).is_zero())
+    }
+
+    /// Returns the evaluations over the `FIELD_ELEMENTS_PER_BLOB`th roots of
+    /// unity, in their natural order, of the polynomial whose evaluations in
+    /// bit-reversed order are the field elements of `blob`.
+    fn blob_to_evaluations(&self, blob: &[u8]) -> Result<Evaluations<Fr, Radix2EvaluationDomain<Fr>>, Eip4844Error> {
+        if blob.len() != BYTES_PER_BLOB {
+            return Err(Eip4844Error::InvalidBlobLength(blob.len()));
+        }
+        let mut evals = blob.chunks_exact(BYTES_PER_FIELD_ELEMENT)
+            .map(|bytes| fr_from_bytes(bytes.try_into().unwrap()))
+            .collect::<Result<Vec<_>, _>>()?;
+        bit_reverse_permutation(&mut evals);
+        Ok(Evaluations::from_vec_and_domain(evals, self.pk.domain))
+    }
+
+    /// Returns the proof of the value of `f` at `z`, along with the value.
+    fn open(&self, f: Evaluations<Fr, Radix2EvaluationDomain<Fr>>, z: Fr) -> Result<(G1Projective, Fr), Eip4844Error> {
+        let f = [f];
+        let y = self.kzg.evaluate_evaluations(&f, &[z])?[0][0];
+        let proof = self.kzg.open_evaluations(&self.pk, &f, &[z], &[vec![y]])?;
+        Ok((proof[0][0], y))
+    }
+}
+
+/// Derives the point at which a blob is opened from the blob and its commitment.
+fn compute_challenge(blob: &[u8], commitment: &Bytes48) -> Fr {
+    let mut hasher = Sha256::new();
+    hasher.update(FIAT_SHAMIR_PROTOCOL_DOMAIN);
+    hasher.update((FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes());
+    hasher.update(blob);
+    hasher.update(commitment);
+    Fr::from_be_bytes_mod_order(&hasher.finalize())
+}
+
+/// Decodes a big-endian field element, rejecting values no less than the modulus.
+fn fr_from_bytes(bytes: &Bytes32) -> Result<Fr, Eip4844Error> {
+    let mut le = *bytes;
+    le.reverse();
+    Fr::deserialize_compressed(le.as_slice()).map_err(|_| Eip4844Error::NonCanonicalFieldElement)
+}
+
+fn fr_to_bytes(x: &Fr) -> Bytes32 {
+    let mut bytes = [0u8; 32];
+    x.serialize_compressed(bytes.as_mut_slice()).expect("a field element is 32 bytes");
+    bytes.reverse();
+    bytes
+}
+
+/// Decodes a compressed point, checking that it is in the prime-order subgroup.
+fn g1_from_bytes(bytes: &Bytes48) -> Result<G1Projective, Eip4844Error> {
+    G1Affine::deserialize_compressed(bytes.as_slice()).map(Into::into).map_err(|_| Eip4844Error::InvalidPoint)
+}
+
+fn g1_to_bytes(p: &G1Projective) -> Bytes48 {
+    let mut bytes = [0u8; 48];
+    p.serialize_compressed(bytes.as_mut_slice()).expect("a point of G1 is 48 bytes");
+    bytes
+}
@@ -1,34 +1,33 @@
-use crate::poly_commit::{PolyCommit};
+use crate::commitment::Commitment;
+use crate::poly_commit::{check_degrees, check_evaluations, check_len, check_non_empty, NonInteractive, PolyCommit, PolyCommitError};
+use crate::transcript::Transcript;
+use crate::srs::{Srs, VerifierKey};
 use ark_ff::{UniformRand, Field};
 use ark_poly::{univariate::{DenseOrSparsePolynomial, DensePolynomial}, DenseUVPolynomial, Polynomial};
-use ark_std::{test_rng, Zero};
+use ark_std::{cfg_into_iter, cfg_iter, rand::{CryptoRng, RngCore}, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use rand::rngs::OsRng;
 use ark_ec::pairing::{Pairing};
 use std::{marker::PhantomData, ops::Mul};
 use crate::utils::poly::eval_poly_over_g1;
 
+/// Domain separator for the transcripts of `NonInteractive` proofs.
+const GWC19_DOMAIN: &[u8] = b"kzg-commitments/gwc19/v1";
+
 /// Struct for implementing the polynomial commitment scheme described in
 /// [this paper](https://eprint.iacr.org/2019/953.pdf).
 pub struct GWC19<E: Pairing> {
-    max_deg: usize,
     _phantom: PhantomData<E>
 }
 
-pub struct GWC_PK<E: Pairing> {
-    /// Corresponds to <g_1, g_1^a, g_1^{a^2}, .... , g_1^{a^t}>
-    pub g1_vec: Vec<E::G1>,
-    /// Corresponds to g_2
-    pub g2_1: E::G2,
-    /// Corresponds to g_2^a
-    pub g2_x: E::G2
-}
-
 /// Implementation of batched polynomial commitments for GWC19
 impl <E: Pairing> PolyCommit<E> for GWC19<E> {
-    type PK = GWC_PK<E>;
-    type SK = E::ScalarField;
+    type PK = Srs<E>;
+    type VK = VerifierKey<E>;
     /// The commitment consists of 1 element of G_1 for each polynomial
     /// committed to.
-    type Commitment = Vec<E::G1>;
+    type Commitment = Vec<Commitment<E>>;
     /// List of the values of a polynomial at each input point.
     type Evaluation = Vec<E::ScalarField>;
     /// We provide a proof element in G_1 for each point.
@@ -38,112 +37,137 @@ impl <E: Pairing> PolyCommit<E> for GWC19<E> {
 
     fn new() -> Self {
         Self {
-            max_deg: 0,
             _phantom: PhantomData
         }
     }
 
     /// Initialises the public key parameters by as described in the paper,
     /// for polynomials of degree up to `max_deg`.
-    fn setup(&mut self, max_deg: usize) -> (Self::PK, Self::SK) {
-        let sk =  E::ScalarField::rand(&mut test_rng());
-        let g1 = E::G1::rand(&mut test_rng());
-        let g2 = E::G2::rand(&mut test_rng());
-        let mut g1_vec = vec![];
-        self.max_deg = max_deg;
-        for i in 0..=max_deg {
-            g1_vec.push(g1.mul(sk.pow(&[i as u64])));
-        };
-        (Self::PK {g1_vec: g1_vec, g2_1: g2, g2_x: g2.mul(sk)}, sk)
+    fn setup_with_rng<R: RngCore + CryptoRng>(&mut self, max_deg: usize, rng: &mut R) -> (Self::PK, Self::VK) {
+        let pk = Srs::generate(max_deg, rng);
+        let vk = pk.verifier_key();
+        (pk, vk)
+    }
+
+    /// Derives the keys for polynomials of degree up to `max_deg` from `srs`.
+    fn trim(&mut self, srs: &Srs<E>, max_deg: usize) -> Result<(Self::PK, Self::VK), PolyCommitError> {
+        srs.trim(max_deg)
     }
 
     /// Commits to the polynomials in `poly`, yields one element of G_1 per
     /// polynomial.
-    fn commit(&self, pk: &Self::PK, polynomials: &[DensePolynomial<E::ScalarField>]) -> Self::Commitment {
-        let mut commitments = vec![];
-        for polynomial in polynomials {
-            if polynomial.degree() > self.max_deg {
-                panic!("Polynomial exceeds maximum degree!");
-            }
-            let mut commitment = E::G1::zero();
-            for i in 0..(polynomial.degree() + 1) {
-                commitment += pk.g1_vec[i].mul(polynomial[i]);
-            }
-            commitments.push(commitment);
-        }
+    fn commit(&self, pk: &Self::PK, polynomials: &[DensePolynomial<E::ScalarField>]) -> Result<Self::Commitment, PolyCommitError> {
+        check_non_empty(polynomials)?;
+        check_degrees(polynomials, pk.g1_vec.len())?;
 
-        if commitments.len() != polynomials.len() {
-            panic!("Commitment failed!")
-        }
-        
-        commitments
+        Ok(cfg_iter!(polynomials).map(|polynomial| Commitment(eval_poly_over_g1::<E>(polynomial, &pk.g1_vec))).collect())
     }
 
     /// Evaluates the polynomials in `poly` at each of the points in `z`, and returns
     /// those values in a vector.
-    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Vec<Self::Evaluation> {
-        let v = poly.iter().map(|p| z.iter().map(|z| p.evaluate(z)).collect()).collect::<Vec<Vec<E::ScalarField>>>();
+    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Result<Vec<Self::Evaluation>, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
 
-        if v.len() * v[0].len() != poly.len() * z.len() {
-            panic!("Evaluation failed!");
-        }
-
-        v
+        Ok(cfg_iter!(poly).map(|p| z.iter().map(|z| p.evaluate(z)).collect()).collect())
     }
 
     /// Creates witnesses to the values of the polynomials in `poly` at all
     /// the points in `z`.
     ///
-    /// It is a logic error for:
-    ///  - `poly.len() != z.len()`
-    ///  - the values in `v` to be incorrect.
-    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<<E as Pairing>::ScalarField>], z: &[<E as Pairing>::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Self::Proof {
-        let mut proofs = vec![];
-        for i in 0..z.len() {
+    /// It is a logic error for the values in `v` to be incorrect.
+    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<<E as Pairing>::ScalarField>], z: &[<E as Pairing>::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<Self::Proof, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
+        check_degrees(poly, pk.g1_vec.len())?;
+        check_evaluations(poly.len(), z.len(), v)?;
+        check_len(z.len(), ver_params.len())?;
+
+        // The witness for each point is independent of the others, so with the
+        // `parallel` feature they are computed on separate tasks.
+        cfg_into_iter!(0..z.len()).map(|i| {
             let mut h = DensePolynomial { coeffs: vec![E::ScalarField::zero()] };
             for j in 0..poly.len() {
                 let f_x = &poly[j];
                 let f_z = v[j][i];
-                let (quot, _rem) = DenseOrSparsePolynomial::from(f_x - DensePolynomial::from_coefficients_slice(&[f_z])).divide_with_q_and_r(&DenseOrSparsePolynomial::from(DensePolynomial::from_coefficients_slice(&[-z[i], E::ScalarField::ONE]))).unwrap();
+                let (quot, _rem) = DenseOrSparsePolynomial::from(f_x - DensePolynomial::from_coefficients_slice(&[f_z])).divide_with_q_and_r(&DenseOrSparsePolynomial::from(DensePolynomial::from_coefficients_slice(&[-z[i], E::ScalarField::ONE]))).ok_or(PolyCommitError::NonInvertible)?;
                 h = h + quot * ver_params[i].pow(&[j as u64]);
             }
 
-            proofs.push(eval_poly_over_g1::<E>(&h, &pk.g1_vec));
-        }
+            Ok(eval_poly_over_g1::<E>(&h, &pk.g1_vec))
+        }).collect()
+    }
 
-        if proofs.len() != z.len() {
-            panic!("Opening/proof witness creation failed!");
-        }
+    /// Verifies that the proof `p` is valid for the given parameters, and
+    /// returns true if it is, and false if it is not. The batching scalars are
+    /// drawn from the operating system's entropy source; see `verify_with_rng`.
+    fn verify(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<bool, PolyCommitError> {
+        Self::verify_with_rng(c, vk, p, z, v, ver_params, &mut OsRng)
+    }
+}
 
-        proofs
+/// The challenges `gamma`, one per point, are squeezed from a transcript over
+/// the verifier key, commitments, points and evaluations.
+impl<E: Pairing> NonInteractive<E> for GWC19<E> {
+    fn open_non_interactive(&self, pk: &Self::PK, c: &Self::Commitment, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<Self::Proof, PolyCommitError> {
+        let ver_params = Self::transcript(&pk.try_verifier_key()?, c, z, v).challenges(b"gamma", z.len());
+        self.open(pk, poly, z, v, &ver_params)
     }
 
-    /// Verifies that the proof `p` is valid for the given parameters, and
-    /// returns true if it is, and false if it is not.
-    fn verify(c: &Self::Commitment, pk: &Self::PK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> bool {
-        let mut rng = test_rng();
+    fn verify_non_interactive(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<bool, PolyCommitError> {
+        let ver_params = Self::transcript(vk, c, z, v).challenges(b"gamma", z.len());
+        Self::verify(c, vk, p, z, v, &ver_params)
+    }
+}
+
+impl<E: Pairing> GWC19<E> {
+    /// Starts the Fiat-Shamir transcript shared by the prover and verifier.
+    fn transcript(vk: &VerifierKey<E>, c: &[Commitment<E>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>]) -> Transcript {
+        let mut transcript = Transcript::new(GWC19_DOMAIN);
+        transcript.append(b"g1", &vk.g1);
+        transcript.append(b"g2_1", &vk.g2_1);
+        transcript.append(b"g2_x", &vk.g2_x);
+        transcript.append(b"commitments", c);
+        transcript.append(b"points", z);
+        transcript.append(b"evaluations", v);
+        transcript
+    }
+
+    /// As `verify`, but draws the scalars `r` used to batch the per-point
+    /// checks into a single pairing equation from `rng`. These must be
+    /// unpredictable to the prover, otherwise proofs can be crafted such that
+    /// the errors at different points cancel out, so `rng` should only be
+    /// deterministic in tests.
+    pub fn verify_with_rng<R: RngCore + CryptoRng>(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &[E::G1], z: &[E::ScalarField], v: &[Vec<E::ScalarField>], ver_params: &[E::ScalarField], rng: &mut R) -> Result<bool, PolyCommitError> {
+        vk.check()?;
+        check_non_empty(c)?;
+        check_non_empty(z)?;
+        check_evaluations(c.len(), z.len(), v)?;
+        check_len(z.len(), p.len())?;
+        check_len(z.len(), ver_params.len())?;
+
         let num_r = z.len();
         let mut r = vec![];
         for _ in 0..num_r {
-            r.push(E::ScalarField::rand(&mut rng));
+            r.push(E::ScalarField::rand(rng));
         }
         r[0] = E::ScalarField::ONE;
 
         // there are poly.len() many commitments
         // there are z.len() many witnesses
-        // there are poly.len() many gamma
+        // there are z.len() many gamma
 
         let mut f = E::G1::zero();
         for i in 0..r.len() {
             let mut g = E::G1::zero();
             for j in 0..c.len() {
-                g += c[j].mul(ver_params[i].pow(&[j as u64]));
+                g += c[j].0.mul(ver_params[i].pow(&[j as u64]));
             }
             let mut h = E::ScalarField::zero();
             for j in 0..c.len() {
                 h += v[j][i] * ver_params[i].pow(&[j as u64]);
             }
-            f += (g - pk.g1_vec[0].mul(h)).mul(r[i]);
+            f += (g - vk.g1.mul(h)).mul(r[i]);
         }
         
         let mut lhs_1 = f;
@@ -156,10 +180,9 @@ impl <E: Pairing> PolyCommit<E> for GWC19<E> {
             rhs_1 += p[i].mul(r[i]);
         }
 
-        let lhs = E::pairing(lhs_1, pk.g2_1);
-        let rhs = E::pairing(rhs_1, pk.g2_x);
+        let lhs = E::pairing(lhs_1, vk.g2_1);
+        let rhs = E::pairing(rhs_1, vk.g2_x);
 
-        lhs == rhs
+        Ok(lhs == rhs)
     }
 }
-
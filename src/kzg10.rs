@@ -1,8 +1,15 @@
-use crate::poly_commit::{PolyCommit};
-use ark_ff::{UniformRand, Field};
-use ark_poly::{univariate::{DenseOrSparsePolynomial, DensePolynomial}, DenseUVPolynomial, Polynomial};
-use ark_std::{test_rng, Zero};
+use crate::commitment::{to_group, Commitment};
+use crate::poly_commit::{check_degrees, check_evaluations, check_len, check_non_empty, NonInteractive, PolyCommit, PolyCommitError};
+use crate::srs::{LagrangeSrs, Srs, VerifierKey};
+use ark_ff::{batch_inversion, FftField, Field, One, UniformRand};
+use ark_poly::{univariate::{DenseOrSparsePolynomial, DensePolynomial}, DenseUVPolynomial, EvaluationDomain, Evaluations, Polynomial, Radix2EvaluationDomain};
+use ark_std::{cfg_iter, rand::{CryptoRng, RngCore}, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use ark_ec::pairing::{Pairing};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::rngs::OsRng;
 use std::{marker::PhantomData, ops::Mul};
 
 use crate::utils::poly::eval_poly_over_g1;
@@ -11,26 +18,16 @@ use crate::utils::poly::eval_poly_over_g1;
 /// [this paper](https://iacr.org/archive/asiacrypt2010/6477178/6477178.pdf),
 /// with modifications made as per our report.
 pub struct KZG10<E: Pairing> {
-    max_deg: usize,
     _phantom: PhantomData<E>
 }
 
-pub struct KZG_PK<E: Pairing> {
-    /// Corresponds to <g_1, g_1^a, g_1^{a^2}, .... , g_1^{a^t}>
-    pub g1_vec: Vec<E::G1>,
-    /// Corresponds to g_2
-    pub g2_1: E::G2,
-    /// Corresponds to g_2^a
-    pub g2_x: E::G2
-}
-
 /// Implementation of batched polynomial commitments for KZG10
 impl <E: Pairing> PolyCommit<E> for KZG10<E> {
-    type PK = KZG_PK<E>;
-    type SK = E::ScalarField;
+    type PK = Srs<E>;
+    type VK = VerifierKey<E>;
     /// The commitment consists of 1 element of G_1 for each polynomial
     /// committed to.
-    type Commitment = Vec<E::G1>;
+    type Commitment = Vec<Commitment<E>>;
     /// List of the values of a polynomial at each input point.
     type Evaluation = Vec<E::ScalarField>;
     /// We provide a proof element in G_1 for each polynomial and point,
@@ -40,106 +37,570 @@ impl <E: Pairing> PolyCommit<E> for KZG10<E> {
 
     fn new() -> Self {
         Self {
-            max_deg: 0,
             _phantom: PhantomData
         }
     }
 
     /// Initialises the public key parameters by as described in the paper,
     /// for polynomials of degree up to `max_deg`.
-    fn setup(&mut self, max_deg: usize) -> (Self::PK, Self::SK) {
-        let sk =  E::ScalarField::rand(&mut test_rng());
-        let g1 = E::G1::rand(&mut test_rng());
-        let g2 = E::G2::rand(&mut test_rng());
-        let mut g1_vec = vec![];
-        self.max_deg = max_deg;
-        for i in 0..(max_deg + 1) {
-            g1_vec.push(g1.mul(sk.pow(&[i as u64])));
-        };
-        (Self::PK {g1_vec: g1_vec, g2_1: g2, g2_x: g2.mul(sk)}, sk)
+    fn setup_with_rng<R: RngCore + CryptoRng>(&mut self, max_deg: usize, rng: &mut R) -> (Self::PK, Self::VK) {
+        let pk = Srs::generate(max_deg, rng);
+        let vk = pk.verifier_key();
+        (pk, vk)
+    }
+
+    /// Derives the keys for polynomials of degree up to `max_deg` from `srs`.
+    fn trim(&mut self, srs: &Srs<E>, max_deg: usize) -> Result<(Self::PK, Self::VK), PolyCommitError> {
+        srs.trim(max_deg)
     }
 
     /// Commits to the polynomials in `poly`, yields one element of G_1 per
     /// polynomial.
-    fn commit(&self, pk: &Self::PK, polynomials: &[DensePolynomial<E::ScalarField>]) -> Self::Commitment {
-        let mut commitments = vec![];
-        for polynomial in polynomials {
-            if polynomial.degree() > self.max_deg {
-                panic!("Polynomial exceeds maximum degree!");
-            }
-            let mut commitment = E::G1::zero();
-            for i in 0..(polynomial.degree() + 1) {
-                commitment += pk.g1_vec[i].mul(polynomial[i]);
-            }
-            commitments.push(commitment);
-        }
+    fn commit(&self, pk: &Self::PK, polynomials: &[DensePolynomial<E::ScalarField>]) -> Result<Self::Commitment, PolyCommitError> {
+        check_non_empty(polynomials)?;
+        check_degrees(polynomials, pk.g1_vec.len())?;
 
-        if commitments.len() != polynomials.len() {
-            panic!("Commitment failed!")
-        }
-        
-        commitments
+        Ok(cfg_iter!(polynomials).map(|polynomial| Commitment(eval_poly_over_g1::<E>(polynomial, &pk.g1_vec))).collect())
     }
 
     /// Evaluates the polynomials in `poly` at each of the points in `z`, and returns
     /// those values in a vector.
-    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Vec<Self::Evaluation> {
-        let v = poly.iter().map(|p| z.iter().map(|z| p.evaluate(z)).collect()).collect::<Vec<Vec<E::ScalarField>>>();
+    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Result<Vec<Self::Evaluation>, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
 
-        if v.len() * v[0].len() != poly.len() * z.len() {
-            panic!("Evaluation failed!");
-        }
-
-        v
+        Ok(cfg_iter!(poly).map(|p| z.iter().map(|z| p.evaluate(z)).collect()).collect())
     }
 
     /// Creates witnesses to the values of the polynomials in `poly` at all
     /// the points in `z`.
     ///
-    /// It is a logic error for:
-    ///  - `poly.len() != z.len()`
-    ///  - the values in `v` to be incorrect.
-    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<<E as Pairing>::ScalarField>], z: &[<E as Pairing>::ScalarField], _: &[Self::Evaluation], _: &()) -> Self::Proof {
-        let mut proofs = vec![];
-        for i in 0..poly.len() {
-            let mut poly_proofs = vec![];
-            for j in 0..z.len() {
-                // For each (polynomial phi, point i) combination, we calculate the polynomial phi(x)-phi(y) and (x-y), and then divide them through.
-                let phi_x = &poly[i];
-                let y = z[j];
-                let phi_y = DensePolynomial::from_coefficients_slice(&[poly[i].evaluate(&y)]);
+    /// It is a logic error for the values in `v` to be incorrect.
+    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<<E as Pairing>::ScalarField>], z: &[<E as Pairing>::ScalarField], _: &[Self::Evaluation], _: &()) -> Result<Self::Proof, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
+        check_degrees(poly, pk.g1_vec.len())?;
 
-                let phi_x_minus_phi_y = phi_x - phi_y;
-                let x_minus_y = DensePolynomial::from_coefficients_slice(&[-y, E::ScalarField::ONE]);
+        // Each (polynomial phi, point y) combination is independent, so with the
+        // `parallel` feature every quotient and its MSM is computed on its own task.
+        cfg_iter!(poly).map(|phi_x| {
+            cfg_iter!(z).map(|&y| Ok(eval_poly_over_g1::<E>(&quotient(phi_x, y)?, &pk.g1_vec))).collect()
+        }).collect()
+    }
 
-                let (quot, _rem) = DenseOrSparsePolynomial::from(phi_x_minus_phi_y).divide_with_q_and_r(&DenseOrSparsePolynomial::from(x_minus_y)).unwrap();
+    /// Verifies that the proof `p` is valid for the given parameters, and
+    /// returns true if it is, and false if it is not. The equation used here
+    /// differs from the one given in the 2010 paper: a proof of its security
+    /// is presented in section 4.1 of our report. Every (polynomial, point)
+    /// check is folded into one multi-pairing with scalars drawn from the
+    /// operating system's entropy source; see `verify_with_rng`.
+    fn verify(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], _ver_params: &()) -> Result<bool, PolyCommitError> {
+        Self::verify_with_rng(c, vk, p, z, v, &mut OsRng)
+    }
+}
 
-                poly_proofs.push(eval_poly_over_g1::<E>(&quot, &pk.g1_vec));
+/// KZG10 has no verifier parameters, so it is already non-interactive: these
+/// are the same as `open` and `verify`.
+impl<E: Pairing> NonInteractive<E> for KZG10<E> {
+    fn open_non_interactive(&self, pk: &Self::PK, _c: &Self::Commitment, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<Self::Proof, PolyCommitError> {
+        self.open(pk, poly, z, v, &())
+    }
+
+    fn verify_non_interactive(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<bool, PolyCommitError> {
+        Self::verify(c, vk, p, z, v, &())
+    }
+}
+
+impl<E: Pairing> KZG10<E> {
+    /// As `verify`, but draws the scalars `r` used to batch the checks from
+    /// `rng`. Each opening satisfies
+    ///     e(c_i - v_ij g + z_j p_ij, g2_1) = e(p_ij, g2_x),
+    /// so with one random `r_ij` per opening, it suffices to check
+    ///     e(sum r_ij (c_i - v_ij g + z_j p_ij), g2_1) = e(sum r_ij p_ij, g2_x),
+    /// which takes a single multi-pairing rather than `2 * c.len() * z.len()`
+    /// pairings. As in `GWC19::verify_with_rng`, `rng` should only be
+    /// deterministic in tests.
+    pub fn verify_with_rng<R: RngCore + CryptoRng>(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &[Vec<E::G1>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>], rng: &mut R) -> Result<bool, PolyCommitError> {
+        Self::check_args(c, vk, p, z, v)?;
+        Ok(Self::batch_check(c, vk, p, z, v, None, rng))
+    }
+
+    /// Checks the openings `p` as described in `verify_with_rng`. If `blinding`
+    /// holds the second generator `h` and the values of the blinding
+    /// polynomials, the openings are instead checked against
+    ///     e(c_i - v_ij g - b_ij h + z_j p_ij, g2_1) = e(p_ij, g2_x).
+    fn batch_check<R: RngCore + CryptoRng>(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &[Vec<E::G1>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>], blinding: Option<(&E::G1, &[Vec<E::ScalarField>])>, rng: &mut R) -> bool {
+        // The scalars multiplying each c_i, g, h and p_ij in the left hand side.
+        let mut c_scalars = vec![E::ScalarField::zero(); c.len()];
+        let mut g_scalar = E::ScalarField::zero();
+        let mut h_scalar = E::ScalarField::zero();
+        let mut p_lhs_scalars = Vec::with_capacity(c.len() * z.len());
+        let mut p_rhs_scalars = Vec::with_capacity(c.len() * z.len());
+        for i in 0..c.len() {
+            for j in 0..z.len() {
+                let r = E::ScalarField::rand(rng);
+                c_scalars[i] += r;
+                g_scalar += r * v[i][j];
+                if let Some((_, b)) = blinding {
+                    h_scalar += r * b[i][j];
+                }
+                p_lhs_scalars.push(r * z[j]);
+                p_rhs_scalars.push(r);
             }
-            proofs.push(poly_proofs);
         }
 
-        if proofs.len() * proofs[0].len() != poly.len() * z.len() {
-            panic!("Opening/proof witness creation failed!");
+        let p = E::G1::normalize_batch(&p.concat());
+        let mut lhs = E::G1::msm_unchecked(&E::G1::normalize_batch(&to_group(c)), &c_scalars) - vk.g1.mul(g_scalar) + E::G1::msm_unchecked(&p, &p_lhs_scalars);
+        if let Some((h, _)) = blinding {
+            lhs -= h.mul(h_scalar);
         }
+        let rhs = E::G1::msm_unchecked(&p, &p_rhs_scalars);
 
-        proofs
+        E::multi_pairing([lhs, -rhs], [vk.g2_1, vk.g2_x]).is_zero()
     }
 
-    /// Verifies that the proof `p` is valid for the given parameters, and
-    /// returns true if it is, and false if it is not. The equation used here
-    /// differs from the one given in the 2010 paper: a proof of its security
-    /// is presented in section 4.1 of our report.
-    fn verify(c: &Self::Commitment, pk: &Self::PK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], _ver_params: &()) -> bool {
+    /// Checks every (polynomial, point) opening on its own with two pairings,
+    /// and returns the indices `(i, j)` of the first invalid opening `p[i][j]`,
+    /// or `None` if they are all valid. This is much slower than `verify`, but
+    /// pinpoints which opening failed.
+    pub fn find_invalid(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &[Vec<E::G1>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>]) -> Result<Option<(usize, usize)>, PolyCommitError> {
+        Self::check_args(c, vk, p, z, v)?;
+
         for i in 0..c.len() {
             for j in 0..z.len() {
-                let lhs = E::pairing(c[i] - pk.g1_vec[0].mul(&v[i][j]), pk.g2_1);
-                let rhs = E::pairing(&p[i][j], pk.g2_x - pk.g2_1.mul(z[j]));
+                let lhs = E::pairing(c[i].0 - vk.g1.mul(&v[i][j]), vk.g2_1);
+                let rhs = E::pairing(p[i][j], vk.g2_x - vk.g2_1.mul(z[j]));
                 if lhs != rhs {
-                    return false;
+                    return Ok(Some((i, j)));
                 }
             }
         }
-        true
+        Ok(None)
+    }
+
+    /// Generates keys for hiding commitments to polynomials of degree up to
+    /// `max_deg`, as in the PolyCommit_Ped scheme of the KZG10 paper. Alongside
+    /// the SRS, the prover key holds the powers <h, h^a, .... , h^{a^t}> of a
+    /// second generator `h` whose discrete logarithm relative to g_1 nobody
+    /// knows. The trapdoor and generators are drawn from the operating system's
+    /// entropy source; see `setup_hiding_with_rng`.
+    pub fn setup_hiding(&mut self, max_deg: usize) -> (HidingProverKey<E>, HidingVerifierKey<E>) {
+        self.setup_hiding_with_rng(max_deg, &mut OsRng)
+    }
+
+    /// As `setup_hiding`, but draws the trapdoor and generators from `rng`.
+    pub fn setup_hiding_with_rng<R: RngCore + CryptoRng>(&mut self, max_deg: usize, rng: &mut R) -> (HidingProverKey<E>, HidingVerifierKey<E>) {
+        let (srs, h_vec) = Srs::<E>::generate_with_h(max_deg, rng);
+        let vk = HidingVerifierKey { vk: srs.verifier_key(), h: h_vec[0].into_group() };
+        (HidingProverKey { srs, h_vec }, vk)
+    }
+
+    /// Commits to the polynomials in `poly` so that the commitments reveal
+    /// nothing about them. Each commitment is g^{phi(a)} h^{phi'(a)} for a
+    /// blinding polynomial phi' drawn from `rng`, of the maximum degree the key
+    /// supports, so that up to that many openings of it reveal nothing either.
+    /// Returns the commitments along with the blinding polynomials, which must
+    /// be kept to open them.
+    pub fn commit_hiding<R: RngCore + CryptoRng>(&self, pk: &HidingProverKey<E>, poly: &[DensePolynomial<E::ScalarField>], rng: &mut R) -> Result<(Vec<Commitment<E>>, Vec<DensePolynomial<E::ScalarField>>), PolyCommitError> {
+        pk.check()?;
+        check_non_empty(poly)?;
+        check_degrees(poly, pk.srs.g1_vec.len())?;
+
+        let blinders: Vec<_> = poly.iter().map(|_| DensePolynomial::rand(pk.srs.max_degree(), rng)).collect();
+        let c = cfg_iter!(poly).zip(cfg_iter!(blinders))
+            .map(|(phi, blinder)| Commitment(eval_poly_over_g1::<E>(phi, &pk.srs.g1_vec) + eval_poly_over_g1::<E>(blinder, &pk.h_vec)))
+            .collect();
+        Ok((c, blinders))
+    }
+
+    /// Creates witnesses to the values of the polynomials in `poly`, committed
+    /// to with `commit_hiding` and the blinding polynomials `blinders`, at all
+    /// the points in `z`. Each witness is g^{psi(a)} h^{psi'(a)} for the
+    /// quotients psi and psi' of phi and phi' by (x - z_j), and comes with the
+    /// value of phi' at z_j, which the verifier needs.
+    pub fn open_hiding(&self, pk: &HidingProverKey<E>, poly: &[DensePolynomial<E::ScalarField>], blinders: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Result<Vec<Vec<HidingProof<E>>>, PolyCommitError> {
+        pk.check()?;
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
+        check_len(poly.len(), blinders.len())?;
+        check_degrees(poly, pk.srs.g1_vec.len())?;
+        check_degrees(blinders, pk.h_vec.len())?;
+
+        cfg_iter!(poly).zip(cfg_iter!(blinders)).map(|(phi, blinder)| {
+            cfg_iter!(z).map(|&y| {
+                let w = eval_poly_over_g1::<E>(&quotient(phi, y)?, &pk.srs.g1_vec) + eval_poly_over_g1::<E>(&quotient(blinder, y)?, &pk.h_vec);
+                Ok(HidingProof { w, blinding_eval: blinder.evaluate(&y) })
+            }).collect()
+        }).collect()
+    }
+
+    /// Verifies openings of hiding commitments, and returns true if they are
+    /// valid, and false if they are not. Each opening satisfies
+    ///     e(c_i - v_ij g - b_ij h + z_j w_ij, g2_1) = e(w_ij, g2_x),
+    /// where b_ij is the value of the blinding polynomial, and the checks are
+    /// batched as in `verify` with scalars drawn from the operating system's
+    /// entropy source; see `verify_hiding_with_rng`.
+    pub fn verify_hiding(c: &[Commitment<E>], vk: &HidingVerifierKey<E>, p: &[Vec<HidingProof<E>>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>]) -> Result<bool, PolyCommitError> {
+        Self::verify_hiding_with_rng(c, vk, p, z, v, &mut OsRng)
+    }
+
+    /// As `verify_hiding`, but draws the batching scalars from `rng`.
+    pub fn verify_hiding_with_rng<R: RngCore + CryptoRng>(c: &[Commitment<E>], vk: &HidingVerifierKey<E>, p: &[Vec<HidingProof<E>>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>], rng: &mut R) -> Result<bool, PolyCommitError> {
+        if vk.h.is_zero() {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        let w: Vec<Vec<E::G1>> = p.iter().map(|p_i| p_i.iter().map(|p_ij| p_ij.w).collect()).collect();
+        let b: Vec<Vec<E::ScalarField>> = p.iter().map(|p_i| p_i.iter().map(|p_ij| p_ij.blinding_eval).collect()).collect();
+        Self::check_args(c, &vk.vk, &w, z, v)?;
+        Ok(Self::batch_check(c, &vk.vk, &w, z, v, Some((&vk.h, &b)), rng))
     }
+
+    /// Returns the key with which to verify degree bounds on polynomials
+    /// committed to with `pk`: the verifier key, along with the maximum degree
+    /// `D` of `pk`, as the bounds are checked relative to it.
+    pub fn bounded_verifier_key(pk: &Srs<E>) -> Result<BoundedVerifierKey<E>, PolyCommitError> {
+        Ok(BoundedVerifierKey { vk: pk.try_verifier_key()?, max_degree: pk.max_degree() })
+    }
+
+    /// Commits to the polynomials in `poly` along with the degree bounds in
+    /// `bounds`, as in Sonic and Marlin. Alongside the usual commitment to each
+    /// polynomial phi of bound d, this commits to the shifted polynomial
+    /// x^{D-d} phi(x), where `D` is the maximum degree of `pk`. That is only
+    /// possible if phi is of degree at most d, as `pk` has no powers above D.
+    pub fn commit_bounded(&self, pk: &Srs<E>, poly: &[DensePolynomial<E::ScalarField>], bounds: &[usize]) -> Result<Vec<BoundedCommitment<E>>, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_len(poly.len(), bounds.len())?;
+        check_degrees(poly, pk.g1_vec.len())?;
+        check_bounds(poly, bounds, pk.max_degree())?;
+
+        Ok(cfg_iter!(poly).zip(cfg_iter!(bounds)).map(|(phi, &d)| BoundedCommitment {
+            c: Commitment(eval_poly_over_g1::<E>(phi, &pk.g1_vec)),
+            shifted: Commitment(eval_poly_over_g1::<E>(phi, &pk.g1_vec[pk.max_degree() - d..])),
+        }).collect())
+    }
+
+    /// Creates witnesses to the values of the polynomials in `poly`, committed
+    /// to with `commit_bounded` and the same `bounds`, at all the points in
+    /// `z`. Each proof holds the witnesses for both phi and x^{D-d} phi(x).
+    pub fn open_bounded(&self, pk: &Srs<E>, poly: &[DensePolynomial<E::ScalarField>], bounds: &[usize], z: &[E::ScalarField]) -> Result<Vec<Vec<BoundedProof<E>>>, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
+        check_len(poly.len(), bounds.len())?;
+        check_degrees(poly, pk.g1_vec.len())?;
+        check_bounds(poly, bounds, pk.max_degree())?;
+
+        cfg_iter!(poly).zip(cfg_iter!(bounds)).map(|(phi, &d)| {
+            let shifted = shift(phi, pk.max_degree() - d);
+            cfg_iter!(z).map(|&y| Ok(BoundedProof {
+                w: eval_poly_over_g1::<E>(&quotient(phi, y)?, &pk.g1_vec),
+                shifted_w: eval_poly_over_g1::<E>(&quotient(&shifted, y)?, &pk.g1_vec),
+            })).collect()
+        }).collect()
+    }
+
+    /// Verifies openings of polynomials committed to with `commit_bounded`,
+    /// and that each is of degree at most its bound in `bounds`. Returns true
+    /// if they are valid, and false if they are not.
+    ///
+    /// Both the commitment to phi and the shifted commitment are checked to
+    /// open to v_ij and z_j^{D-d} v_ij at each z_j, in one batched check as in
+    /// `verify`. Agreement at a point chosen after the commitments shows that
+    /// the shifted commitment is to x^{D-d} phi(x) itself, and so the points in
+    /// `z` must not be known to the prover before it commits. The batching
+    /// scalars are drawn from the operating system's entropy source; see
+    /// `verify_bounded_with_rng`.
+    pub fn verify_bounded(c: &[BoundedCommitment<E>], vk: &BoundedVerifierKey<E>, bounds: &[usize], p: &[Vec<BoundedProof<E>>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>]) -> Result<bool, PolyCommitError> {
+        Self::verify_bounded_with_rng(c, vk, bounds, p, z, v, &mut OsRng)
+    }
+
+    /// As `verify_bounded`, but draws the batching scalars from `rng`.
+    pub fn verify_bounded_with_rng<R: RngCore + CryptoRng>(c: &[BoundedCommitment<E>], vk: &BoundedVerifierKey<E>, bounds: &[usize], p: &[Vec<BoundedProof<E>>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>], rng: &mut R) -> Result<bool, PolyCommitError> {
+        check_non_empty(c)?;
+        check_len(c.len(), bounds.len())?;
+        check_len(c.len(), p.len())?;
+        check_len(c.len(), v.len())?;
+        if let Some(&d) = bounds.iter().find(|&&d| d > vk.max_degree) {
+            return Err(PolyCommitError::DegreeTooLarge { degree: d, max_degree: vk.max_degree });
+        }
+
+        // Each polynomial and its shifted counterpart is checked as a separate
+        // polynomial, with the shifted values computed by the verifier.
+        let mut c_ = Vec::with_capacity(2 * c.len());
+        let mut p_ = Vec::with_capacity(2 * c.len());
+        let mut v_ = Vec::with_capacity(2 * c.len());
+        for (((c_i, &d), p_i), v_i) in c.iter().zip(bounds).zip(p).zip(v) {
+            c_.extend([c_i.c, c_i.shifted]);
+            p_.push(p_i.iter().map(|p_ij| p_ij.w).collect());
+            p_.push(p_i.iter().map(|p_ij| p_ij.shifted_w).collect());
+            v_.push(v_i.clone());
+            v_.push(z.iter().zip(v_i).map(|(z_j, v_ij)| z_j.pow([(vk.max_degree - d) as u64]) * v_ij).collect());
+        }
+        Self::verify_with_rng(&c_, &vk.vk, &p_, z, &v_, rng)
+    }
+
+    /// Commits to the polynomials given by their evaluations `evals` over the
+    /// domain of `pk`, without interpolating them. Each commitment is the MSM
+    /// of the evaluations with the Lagrange basis, and is the same as that of
+    /// `commit` on the interpolated polynomial.
+    pub fn commit_evaluations(&self, pk: &LagrangeSrs<E>, evals: &[Evaluations<E::ScalarField, Radix2EvaluationDomain<E::ScalarField>>]) -> Result<Vec<Commitment<E>>, PolyCommitError> {
+        check_non_empty(evals)?;
+        check_domains(pk.domain, evals)?;
+
+        Ok(cfg_iter!(evals).map(|f| Commitment(E::G1::msm_unchecked(&pk.lagrange_vec, &f.evals))).collect())
+    }
+
+    /// Evaluates the polynomials given by `evals` at each of the points in `z`.
+    /// Points outside the domain are evaluated with the barycentric formula
+    ///     f(z) = (z^n - 1)/n \sum_i f_i w^i / (z - w^i)
+    /// in O(n) field operations, and points in it are simply looked up. The
+    /// domain must be a subgroup of roots of unity rather than a coset of one.
+    pub fn evaluate_evaluations(&self, evals: &[Evaluations<E::ScalarField, Radix2EvaluationDomain<E::ScalarField>>], z: &[E::ScalarField]) -> Result<Vec<Vec<E::ScalarField>>, PolyCommitError> {
+        check_non_empty(evals)?;
+        check_non_empty(z)?;
+        check_domains(evals[0].domain(), evals)?;
+        if !evals[0].domain().coset_offset().is_one() {
+            return Err(PolyCommitError::DomainMismatch);
+        }
+
+        let points: Vec<_> = z.iter().map(|&y| DomainPoint::new(evals[0].domain(), y)).collect();
+        Ok(cfg_iter!(evals).map(|f| points.iter().map(|point| point.evaluate(f)).collect()).collect())
+    }
+
+    /// Creates witnesses to the values `v` of the polynomials given by `evals`
+    /// at all the points in `z`, as computed by `evaluate_evaluations`. The
+    /// quotient (f(x) - f(z)) / (x - z) is computed in evaluation form over
+    /// the domain, and committed to with the Lagrange basis, so the proofs
+    /// are the same as those of `open` on the interpolated polynomials.
+    ///
+    /// It is a logic error for the values in `v` to be incorrect.
+    pub fn open_evaluations(&self, pk: &LagrangeSrs<E>, evals: &[Evaluations<E::ScalarField, Radix2EvaluationDomain<E::ScalarField>>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>]) -> Result<Vec<Vec<E::G1>>, PolyCommitError> {
+        check_non_empty(evals)?;
+        check_non_empty(z)?;
+        check_domains(pk.domain, evals)?;
+        check_len(evals.len(), v.len())?;
+        for v_i in v {
+            check_len(z.len(), v_i.len())?;
+        }
+
+        let points: Vec<_> = z.iter().map(|&y| DomainPoint::new(pk.domain, y)).collect();
+        Ok(cfg_iter!(evals).zip(cfg_iter!(v)).map(|(f, v_i)| {
+            points.iter().zip(v_i).map(|(point, &y)| E::G1::msm_unchecked(&pk.lagrange_vec, &point.quotient(f, y))).collect()
+        }).collect())
+    }
+
+    /// Creates witnesses to the values of the polynomials in `poly` at every
+    /// element of `domain`, in the order of `domain.elements()`, with the
+    /// technique of Feist and Khovratovich (FK20). The proofs are the same as
+    /// those of `open` with `z` the elements of `domain`, but are computed
+    /// with O(n log n) group operations per polynomial, rather than the O(n^2)
+    /// of one quotient and MSM per point.
+    ///
+    /// For a polynomial phi of degree d, the witness at z is sum_m h_m z^m,
+    /// where
+    ///     h_m = sum_{j=0}^{d-1-m} phi_{m+1+j} g^{a^j},
+    /// and so all of the witnesses are the FFT of the h_m over `domain`. The
+    /// h_m are a Toeplitz matrix-vector product of the coefficients with the
+    /// SRS, which is computed as a convolution with FFTs in G_1.
+    pub fn open_all_on_domain(&self, pk: &Srs<E>, poly: &[DensePolynomial<E::ScalarField>], domain: Radix2EvaluationDomain<E::ScalarField>) -> Result<Vec<Vec<E::G1>>, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_degrees(poly, pk.g1_vec.len())?;
+
+        cfg_iter!(poly).map(|phi| fk20::<E>(phi, &pk.g1_vec, domain)).collect()
+    }
+
+    /// Fails unless there is one proof and evaluation per (polynomial, point),
+    /// and `vk` is well-formed.
+    fn check_args(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &[Vec<E::G1>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>]) -> Result<(), PolyCommitError> {
+        vk.check()?;
+        check_non_empty(c)?;
+        check_non_empty(z)?;
+        check_len(c.len(), p.len())?;
+        check_evaluations(c.len(), z.len(), v)?;
+        for p_i in p {
+            check_len(z.len(), p_i.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Prover key for hiding commitments: the SRS, along with the powers of the
+/// same trapdoor over the second generator `h`.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct HidingProverKey<E: Pairing> {
+    pub srs: Srs<E>,
+    /// Corresponds to <h, h^a, h^{a^2}, .... , h^{a^t}>
+    pub h_vec: Vec<E::G1Affine>,
+}
+
+/// Verifier key for hiding commitments.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct HidingVerifierKey<E: Pairing> {
+    pub vk: VerifierKey<E>,
+    /// Corresponds to h
+    pub h: E::G1,
+}
+
+/// Opening of a hiding commitment at one point.
+#[derive(Debug, Clone, Copy, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct HidingProof<E: Pairing> {
+    /// Corresponds to g^{psi(a)} h^{psi'(a)}
+    pub w: E::G1,
+    /// The value of the blinding polynomial at the point.
+    pub blinding_eval: E::ScalarField,
+}
+
+impl<E: Pairing> HidingProverKey<E> {
+    /// Fails unless the key is non-empty and there are as many powers of `h`
+    /// as of g_1.
+    fn check(&self) -> Result<(), PolyCommitError> {
+        if self.srs.g1_vec.is_empty() || self.h_vec.len() != self.srs.g1_vec.len() {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        Ok(())
+    }
+}
+
+/// Key with which to verify degree bounds; see `KZG10::bounded_verifier_key`.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BoundedVerifierKey<E: Pairing> {
+    pub vk: VerifierKey<E>,
+    /// The maximum degree `D` of the prover key.
+    pub max_degree: usize,
+}
+
+/// Commitment to a polynomial phi with a degree bound d.
+#[derive(Debug, Clone, Copy, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BoundedCommitment<E: Pairing> {
+    /// Corresponds to g^{phi(a)}
+    pub c: Commitment<E>,
+    /// Corresponds to g^{a^{D-d} phi(a)}
+    pub shifted: Commitment<E>,
+}
+
+/// Opening of a commitment with a degree bound at one point.
+#[derive(Debug, Clone, Copy, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BoundedProof<E: Pairing> {
+    /// The witness for phi.
+    pub w: E::G1,
+    /// The witness for x^{D-d} phi(x).
+    pub shifted_w: E::G1,
+}
+
+/// Fails unless every bound is at most `max_deg`, and every polynomial is of
+/// degree at most its bound.
+fn check_bounds<F: Field>(poly: &[DensePolynomial<F>], bounds: &[usize], max_deg: usize) -> Result<(), PolyCommitError> {
+    for (phi, &d) in poly.iter().zip(bounds) {
+        if d > max_deg {
+            return Err(PolyCommitError::DegreeTooLarge { degree: d, max_degree: max_deg });
+        }
+        if phi.degree() > d {
+            return Err(PolyCommitError::DegreeTooLarge { degree: phi.degree(), max_degree: d });
+        }
+    }
+    Ok(())
+}
+
+/// Fails unless every set of evaluations is over `domain`.
+fn check_domains<F: FftField>(domain: Radix2EvaluationDomain<F>, evals: &[Evaluations<F, Radix2EvaluationDomain<F>>]) -> Result<(), PolyCommitError> {
+    for f in evals {
+        check_len(domain.size(), f.evals.len())?;
+        if f.domain() != domain {
+            return Err(PolyCommitError::DomainMismatch);
+        }
+    }
+    Ok(())
+}
+
+/// A point `z` along with the inverses of (w^i - z) for each element w^i of a
+/// domain, which are shared by every polynomial evaluated or opened at `z`.
+struct DomainPoint<F: FftField> {
+    z: F,
+    domain: Radix2EvaluationDomain<F>,
+    /// The index `k` such that z = w^k, if `z` is in the domain.
+    index: Option<usize>,
+    /// The inverses of (w^i - z), with 0 in place of that at `index`.
+    inverses: Vec<F>,
+}
+
+impl<F: FftField> DomainPoint<F> {
+    fn new(domain: Radix2EvaluationDomain<F>, z: F) -> Self {
+        let mut inverses: Vec<F> = domain.elements().map(|w_i| w_i - z).collect();
+        let index = inverses.iter().position(|d| d.is_zero());
+        batch_inversion(&mut inverses);
+        Self { z, domain, index, inverses }
+    }
+
+    /// Returns f(z) for the polynomial f given by its evaluations `f`.
+    fn evaluate(&self, f: &Evaluations<F, Radix2EvaluationDomain<F>>) -> F {
+        if let Some(k) = self.index {
+            return f.evals[k];
+        }
+        let sum: F = self.domain.elements().zip(&f.evals).zip(&self.inverses).map(|((w_i, f_i), inv)| w_i * f_i * inv).sum();
+        -sum * self.domain.evaluate_vanishing_polynomial(self.z) * self.domain.size_inv()
+    }
+
+    /// Returns the evaluations of (f(x) - y) / (x - z) over the domain, where
+    /// y = f(z). At z = w^k itself, the quotient takes the value
+    ///     -1/z \sum_{i != k} w^i (f_i - y) / (w^i - z).
+    fn quotient(&self, f: &Evaluations<F, Radix2EvaluationDomain<F>>, y: F) -> Vec<F> {
+        let mut q: Vec<F> = f.evals.iter().zip(&self.inverses).map(|(f_i, inv)| (*f_i - y) * inv).collect();
+        if let Some(k) = self.index {
+            let sum: F = self.domain.elements().zip(&q).map(|(w_i, q_i)| w_i * q_i).sum();
+            q[k] = -sum / self.z;
+        }
+        q
+    }
+}
+
+/// Returns x^k phi(x).
+fn shift<F: Field>(phi: &DensePolynomial<F>, k: usize) -> DensePolynomial<F> {
+    if phi.is_zero() {
+        return phi.clone();
+    }
+    let mut coeffs = vec![F::zero(); k];
+    coeffs.extend_from_slice(&phi.coeffs);
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// Returns the witnesses to the values of `phi` at every element of `domain`,
+/// as described in `KZG10::open_all_on_domain`.
+fn fk20<E: Pairing>(phi: &DensePolynomial<E::ScalarField>, srs: &[E::G1Affine], domain: Radix2EvaluationDomain<E::ScalarField>) -> Result<Vec<E::G1>, PolyCommitError> {
+    let n = domain.size();
+    if phi.coeffs.len() <= 1 {
+        return Ok(vec![E::G1::zero(); n]);
+    }
+    let d = phi.degree();
+
+    // With the coefficients reversed, h_m is the (d-1-m)th term of their
+    // convolution with <g, g^a, .... , g^{a^{d-1}}>, which has 2d terms and so
+    // does not wrap around in a domain of size at least 2d.
+    let big = Radix2EvaluationDomain::<E::ScalarField>::new(2 * d).ok_or(PolyCommitError::DegreeTooLarge { degree: d, max_degree: srs.len() - 1 })?;
+    let powers: Vec<E::G1> = srs[..d].iter().map(|g| g.into_group()).collect();
+    let reversed: Vec<E::ScalarField> = phi.coeffs.iter().rev().copied().collect();
+    let products: Vec<E::G1> = big.fft(&powers).into_iter().zip(big.fft(&reversed)).map(|(g, f)| g * f).collect();
+    let conv = big.ifft(&products);
+
+    // Above the size of the domain, z^m wraps around to c^n z^{m - n} for the
+    // offset c of the domain, which is 1 unless it is a coset.
+    let mut h = vec![E::G1::zero(); n];
+    let mut wrap = E::ScalarField::ONE;
+    for m in 0..d {
+        if m > 0 && m % n == 0 {
+            wrap *= domain.coset_offset_pow_size();
+        }
+        h[m % n] += conv[d - 1 - m] * wrap;
+    }
+    Ok(domain.fft(&h))
+}
+
+/// Returns the quotient of phi(x) - phi(y) by (x - y).
+fn quotient<F: Field>(phi_x: &DensePolynomial<F>, y: F) -> Result<DensePolynomial<F>, PolyCommitError> {
+    // We calculate the polynomial phi(x)-phi(y) and (x-y), and then divide them through.
+    let phi_y = DensePolynomial::from_coefficients_slice(&[phi_x.evaluate(&y)]);
+
+    let phi_x_minus_phi_y = phi_x - phi_y;
+    let x_minus_y = DensePolynomial::from_coefficients_slice(&[-y, F::ONE]);
+
+    let (quot, _rem) = DenseOrSparsePolynomial::from(phi_x_minus_phi_y).divide_with_q_and_r(&DenseOrSparsePolynomial::from(x_minus_y)).ok_or(PolyCommitError::NonInvertible)?;
+    Ok(quot)
 }
@@ -3,5 +3,13 @@
 pub mod kzg10;
 pub mod gwc19;
 pub mod poly_commit;
+pub mod commitment;
 pub mod utils;
 pub mod djba21;
+pub mod shplonk;
+pub mod pst13;
+pub mod ceremony;
+pub mod srs;
+pub mod trusted_setup;
+pub mod transcript;
+pub mod eip4844;
@@ -1,3 +1,235 @@
-fn main() {
-    println!("eh");
+//! Command-line interface for producing and checking proofs without writing
+//! Rust. Keys, commitments, evaluations and proofs are written in the crate's
+//! serialized format (`CanonicalSerialize`, compressed), and proofs are made
+//! non-interactive with `NonInteractive`. See `--help` for usage.
+
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_poly::{DenseUVPolynomial, univariate::DensePolynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{SeedableRng, rngs::StdRng};
+use clap::{Arg, ArgMatches, Command, value_parser};
+use kzg_commitments::commitment::Commitment;
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::NonInteractive;
+use kzg_commitments::shplonk::SHPLONK;
+use kzg_commitments::srs::{Srs, VerifierKey};
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn cli() -> Command {
+    let file = |name: &'static str, help: &'static str| Arg::new(name).long(name).value_name("FILE").value_parser(value_parser!(PathBuf)).required(true).help(help);
+
+    Command::new("kzg-commitments")
+        .about("Commit to polynomials, and create and verify batched evaluation proofs")
+        .subcommand_required(true)
+        .arg(Arg::new("scheme").long("scheme").global(true).value_parser(["kzg10", "gwc19", "djba21", "shplonk"]).default_value("kzg10").help("Commitment scheme"))
+        .arg(Arg::new("curve").long("curve").global(true).value_parser(["bls12-381", "bls12-377", "bn254"]).default_value("bls12-381").help("Pairing-friendly curve"))
+        .subcommand(Command::new("setup")
+            .about("Generate prover and verifier keys for polynomials of degree up to --degree")
+            .arg(Arg::new("degree").long("degree").value_name("N").value_parser(value_parser!(usize)).required(true).help("Maximum degree of the polynomials"))
+            .arg(Arg::new("seed").long("seed").value_name("N").value_parser(value_parser!(u64)).help("Derive the trapdoor from this seed. Anyone who knows it can forge proofs, so only use this for testing"))
+            .arg(file("pk", "Output file for the prover key"))
+            .arg(file("vk", "Output file for the verifier key")))
+        .subcommand(Command::new("commit")
+            .about("Commit to polynomials")
+            .arg(file("pk", "Prover key"))
+            .arg(file("polys", "Polynomials, one per line, as decimal coefficients from the constant term up"))
+            .arg(file("commitment", "Output file for the commitments")))
+        .subcommand(Command::new("open")
+            .about("Evaluate polynomials at points, and prove the evaluations")
+            .arg(file("pk", "Prover key"))
+            .arg(file("polys", "Polynomials, one per line, as decimal coefficients from the constant term up"))
+            .arg(file("points", "Points, one per line, in decimal"))
+            .arg(file("commitment", "Commitments to the polynomials"))
+            .arg(file("evaluations", "Output file for the evaluations"))
+            .arg(file("proof", "Output file for the proof")))
+        .subcommand(Command::new("verify")
+            .about("Verify a proof; exits with status 1 if it is invalid")
+            .arg(file("vk", "Verifier key"))
+            .arg(file("commitment", "Commitments to the polynomials"))
+            .arg(file("points", "Points, one per line, in decimal"))
+            .arg(file("evaluations", "Evaluations of the polynomials"))
+            .arg(file("proof", "Proof of the evaluations")))
+        .subcommand(Command::new("inspect")
+            .about("Print the contents of a key, commitment, evaluations or proof file")
+            .arg(Arg::new("kind").long("kind").value_parser(["pk", "vk", "commitment", "evaluations", "proof"]).required(true).help("What the file contains"))
+            .arg(Arg::new("file").value_name("FILE").value_parser(value_parser!(PathBuf)).required(true)))
+}
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+    let scheme = matches.get_one::<String>("scheme").unwrap().as_str();
+    let result = match matches.get_one::<String>("curve").unwrap().as_str() {
+        "bls12-381" => with_scheme::<Bls12_381>(scheme, &matches),
+        "bls12-377" => with_scheme::<Bls12_377>(scheme, &matches),
+        "bn254" => with_scheme::<Bn254>(scheme, &matches),
+        _ => unreachable!(),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn with_scheme<E: Pairing>(scheme: &str, matches: &ArgMatches) -> Result<bool> {
+    match scheme {
+        "kzg10" => run::<E, KZG10<E>>(matches),
+        "gwc19" => run::<E, GWC19<E>>(matches),
+        "djba21" => run::<E, DJBA21<E>>(matches),
+        "shplonk" => run::<E, SHPLONK<E>>(matches),
+        _ => unreachable!(),
+    }
+}
+
+/// Runs the subcommand in `matches`, and returns whether it succeeded. Only
+/// `verify` can fail without an error, when the proof is invalid.
+fn run<E: Pairing, P>(matches: &ArgMatches) -> Result<bool>
+where
+    P: NonInteractive<E, PK = Srs<E>, VK = VerifierKey<E>, Commitment = Vec<Commitment<E>>>,
+{
+    let (command, m) = matches.subcommand().unwrap();
+    let path = |name: &str| m.get_one::<PathBuf>(name).unwrap();
+
+    match command {
+        "setup" => {
+            let degree = *m.get_one::<usize>("degree").unwrap();
+            let mut pc = P::new();
+            let (pk, vk) = match m.get_one::<u64>("seed") {
+                Some(&seed) => pc.setup_with_rng(degree, &mut StdRng::seed_from_u64(seed)),
+                None => pc.setup(degree),
+            };
+            write(path("pk"), &pk)?;
+            write(path("vk"), &vk)?;
+        }
+        "commit" => {
+            let pk: P::PK = read(path("pk"))?;
+            let poly = read_polys::<E::ScalarField>(path("polys"))?;
+            write(path("commitment"), &P::new().commit(&pk, &poly)?)?;
+        }
+        "open" => {
+            let pk: P::PK = read(path("pk"))?;
+            let poly = read_polys::<E::ScalarField>(path("polys"))?;
+            let z = read_points::<E::ScalarField>(path("points"))?;
+            let c: P::Commitment = read(path("commitment"))?;
+            let pc = P::new();
+            let v = pc.evaluate(&poly, &z)?;
+            let p = pc.open_non_interactive(&pk, &c, &poly, &z, &v)?;
+            write(path("evaluations"), &v)?;
+            write(path("proof"), &p)?;
+        }
+        "verify" => {
+            let vk: P::VK = read(path("vk"))?;
+            let c: P::Commitment = read(path("commitment"))?;
+            let z = read_points::<E::ScalarField>(path("points"))?;
+            let v: Vec<P::Evaluation> = read(path("evaluations"))?;
+            let p: P::Proof = read(path("proof"))?;
+            let valid = P::verify_non_interactive(&c, &vk, &p, &z, &v)?;
+            println!("{}", if valid { "valid" } else { "invalid" });
+            return Ok(valid);
+        }
+        "inspect" => {
+            let file = path("file");
+            match m.get_one::<String>("kind").unwrap().as_str() {
+                "pk" => {
+                    let pk: P::PK = read(file)?;
+                    let Some(g1) = pk.g1_vec.first() else {
+                        return Err("prover key is empty".into());
+                    };
+                    println!("prover key: max degree {}", pk.max_degree());
+                    println!("g1: {}", to_hex(g1));
+                    println!("g2_1: {}", to_hex(&pk.g2_1));
+                    println!("g2_x: {}", to_hex(&pk.g2_x));
+                }
+                "vk" => {
+                    let vk: P::VK = read(file)?;
+                    println!("g1: {}", to_hex(&vk.g1));
+                    println!("g2_1: {}", to_hex(&vk.g2_1));
+                    println!("g2_x: {}", to_hex(&vk.g2_x));
+                }
+                "commitment" => {
+                    let c: P::Commitment = read(file)?;
+                    println!("{} commitments", c.len());
+                    for (i, c) in c.iter().enumerate() {
+                        println!("{i}: {}", to_hex(c));
+                    }
+                }
+                "evaluations" => {
+                    let v: Vec<P::Evaluation> = read(file)?;
+                    println!("evaluations of {} polynomials, {} bytes", v.len(), v.compressed_size());
+                }
+                "proof" => {
+                    let p: P::Proof = read(file)?;
+                    println!("proof: {} bytes", p.compressed_size());
+                    println!("{}", to_hex(&p));
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+    Ok(true)
+}
+
+fn write<T: CanonicalSerialize>(path: &PathBuf, value: &T) -> Result<()> {
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value.serialize_compressed(&mut bytes)?;
+    std::fs::write(path, bytes).map_err(|e| format!("could not write {}: {e}", path.display()).into())
+}
+
+/// Reads a value written by `write`, checking that it is valid and that the
+/// whole file was consumed.
+fn read<T: CanonicalDeserialize>(path: &PathBuf) -> Result<T> {
+    let bytes = std::fs::read(path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    let mut reader = bytes.as_slice();
+    let value = T::deserialize_compressed(&mut reader).map_err(|e| format!("invalid contents of {}: {e}", path.display()))?;
+    if !reader.is_empty() {
+        return Err(format!("unexpected trailing bytes in {}", path.display()).into());
+    }
+    Ok(value)
+}
+
+/// Reads one line of decimal field elements per entry, skipping blank lines
+/// and those starting with `#`.
+fn read_lines<F: PrimeField>(path: &PathBuf) -> Result<Vec<Vec<F>>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    contents.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(n, line)| {
+            line.split_whitespace()
+                .map(|x| F::from_str(x).map_err(|_| format!("{}:{}: `{x}` is not a field element", path.display(), n + 1).into()))
+                .collect()
+        })
+        .collect()
+}
+
+fn read_polys<F: PrimeField>(path: &PathBuf) -> Result<Vec<DensePolynomial<F>>> {
+    Ok(read_lines(path)?.into_iter().map(DensePolynomial::from_coefficients_vec).collect())
+}
+
+fn read_points<F: PrimeField>(path: &PathBuf) -> Result<Vec<F>> {
+    let lines = read_lines::<F>(path)?;
+    if lines.iter().any(|line| line.len() != 1) {
+        return Err(format!("{}: expected one point per line", path.display()).into());
+    }
+    Ok(lines.into_iter().flatten().collect())
+}
+
+fn to_hex<T: CanonicalSerialize>(value: &T) -> String {
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value.serialize_compressed(&mut bytes).expect("serializing to a Vec cannot fail");
+    hex::encode(bytes)
 }
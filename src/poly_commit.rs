@@ -1,55 +1,203 @@
 use ark_ec::pairing::Pairing;
-use ark_poly::univariate::DensePolynomial;
+use ark_ff::Field;
+use ark_poly::{univariate::DensePolynomial, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use crate::srs::Srs;
+use rand::rngs::OsRng;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Errors returned by the polynomial commitment schemes when they are given
+/// arguments they cannot commit to, open or verify.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolyCommitError {
+    /// A polynomial has a higher degree than the prover key supports.
+    DegreeTooLarge { degree: usize, max_degree: usize },
+    /// Two arguments which must correspond element-wise differ in length.
+    LengthMismatch { expected: usize, found: usize },
+    /// The evaluation point at the given index already appears earlier.
+    DuplicatePoint(usize),
+    /// No polynomials, points or evaluations were given.
+    EmptyInput,
+    /// The prover or verifier key is empty or contains the identity.
+    MalformedKey,
+    /// A denominator which must be inverted is zero.
+    NonInvertible,
+    /// Evaluations are given over a different domain from that of the key.
+    DomainMismatch,
+}
+
+impl fmt::Display for PolyCommitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DegreeTooLarge { degree, max_degree } => write!(f, "polynomial of degree {degree} exceeds maximum degree {max_degree}"),
+            Self::LengthMismatch { expected, found } => write!(f, "expected {expected} elements, found {found}"),
+            Self::DuplicatePoint(i) => write!(f, "evaluation point {i} is a duplicate"),
+            Self::EmptyInput => write!(f, "empty input"),
+            Self::MalformedKey => write!(f, "malformed key"),
+            Self::NonInvertible => write!(f, "attempted to invert zero"),
+            Self::DomainMismatch => write!(f, "evaluations are over a different domain from the key"),
+        }
+    }
+}
+
+impl std::error::Error for PolyCommitError {}
 
 /// Generic trait which implements the interface for a batched polynomial commitment.
 /// Instances of the class should be instantiated by the prover, and the verifier only
-/// needs to use the `verify` function.
+/// needs to use the `verify` function, along with the constant-size verifier key.
+///
+/// Any number of polynomials may be opened at any number of points: every polynomial is
+/// opened at every point. Neither the degrees of the polynomials nor the number of
+/// polynomials or points need be powers of 2.
+///
+/// Arguments which can be detected as invalid, such as polynomials of too high a degree or
+/// slices of the wrong length, are rejected with a `PolyCommitError` rather than a panic.
+/// `verify` returns `Ok(false)` only for well-formed arguments whose proofs are invalid.
 ///
-/// Importantly for our applications, the number of polynomials committed to and the number
-/// of points must be the same. Providing a differing number of polynomials and points is
-/// considered a logic error. Additionally, the degrees of the polynomials, as well
-/// as the number of points and polynomials committed to, must be powers of 2. It is
-/// a logic error for this to not be the case.
+/// The keys, commitments, evaluations and proofs may all be written to and read from
+/// bytes with `CanonicalSerialize` and `CanonicalDeserialize`, e.g. to store an SRS or
+/// to send a proof to a verifier.
 ///
 pub trait PolyCommit<E: Pairing> {
-    /// Public key
-    type PK;
-    /// Secret key. For our applications this value can be safely discarded.
-    type SK;
+    /// Prover key, which holds every power of the SRS needed to commit and open.
+    type PK: CanonicalSerialize + CanonicalDeserialize;
+    /// Verifier key, which only holds the few group elements needed to verify.
+    type VK: CanonicalSerialize + CanonicalDeserialize;
     /// Commitment to a collection of polynomials.
-    type Commitment;
+    type Commitment: CanonicalSerialize + CanonicalDeserialize;
     /// Values of the collection of polynomial over some set of points.
     /// It is important to note that these values are not necessarily lists of values:
     /// in some cases it is more expedient to return a polynomial of low degree
     /// which agrees with the polynomial for all the given values.
-    type Evaluation;
+    type Evaluation: CanonicalSerialize + CanonicalDeserialize;
     /// Proofs of the evaluations of a collection of polynomials over a set of points.
-    type Proof;
+    type Proof: CanonicalSerialize + CanonicalDeserialize;
     /// Some procedures require the verifier to provide random arguments. These arguments
     /// are packaged into this type.
     type VerifierParams;
 
     fn new() -> Self;
 
-    /// Initialises the polynomial commit for polynomial up to degreee `max_deg`.
-    fn setup(&mut self, max_deg: usize) -> (Self::PK, Self::SK); // throw away SK
+    /// Initialises the polynomial commit for polynomial up to degreee `max_deg`,
+    /// and returns the prover and verifier keys.
+    /// The trapdoor and generators are drawn from the operating system's
+    /// entropy source, and the trapdoor is discarded.
+    fn setup(&mut self, max_deg: usize) -> (Self::PK, Self::VK) {
+        self.setup_with_rng(max_deg, &mut OsRng)
+    }
+
+    /// As `setup`, but draws the trapdoor and generators from `rng`. This is
+    /// mostly useful for tests and benchmarks which want a reproducible key:
+    /// anyone who knows the state of `rng` also knows the trapdoor.
+    fn setup_with_rng<R: RngCore + CryptoRng>(&mut self, max_deg: usize, rng: &mut R) -> (Self::PK, Self::VK);
+
+    /// Initialises the polynomial commit for polynomials up to degree `max_deg`
+    /// using an existing (possibly much larger) `srs`, and returns the prover
+    /// and verifier keys. Fails if `max_deg` exceeds the maximum degree
+    /// supported by `srs`.
+    fn trim(&mut self, srs: &Srs<E>, max_deg: usize) -> Result<(Self::PK, Self::VK), PolyCommitError>;
 
     /// Using the public key parameters `pk`, this function commits to all of the polynomials
     /// in `poly` and returns the commitment.
-    fn commit(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>]) -> Self::Commitment;
+    fn commit(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>]) -> Result<Self::Commitment, PolyCommitError>;
 
     /// Evaluates the given polynomials in `poly` at each of the points in `z`, and returns the
-    /// values. Importantly, the length of `poly` must match the length of `z`: it is a logic
-    /// error for this not to be the case.
-    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Vec<Self::Evaluation>;
+    /// values.
+    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Result<Vec<Self::Evaluation>, PolyCommitError>;
 
     /// Creates witness for all of the polynomials in `poly` evaluated at all of the points in `z`.
-    /// As above, it is a logic error for the length of `poly` to not be the same as the length of
-    /// `z`.
     /// This function will assume that the values in `v` are accurate.
-    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Self::Proof;
+    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<Self::Proof, PolyCommitError>;
 
     /// Verifies, given the commitments to the polynomials in `c`, that the proofs in `p` are valid
     /// for the points in `z`. Returns true if the proofs are valid, and false if not.
-    fn verify(c: &Self::Commitment, pk: &Self::PK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> bool;
+    fn verify(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<bool, PolyCommitError>;
+}
+
+/// Non-interactive variant of a batched polynomial commitment, in which the verifier
+/// parameters are derived with a Fiat-Shamir `Transcript` from the verifier key,
+/// commitments, points and evaluations (and, where the protocol requires it, from the
+/// proof itself), rather than being supplied by the caller. The transcripts of each
+/// scheme are domain-separated, so a proof for one scheme can't be replayed as a proof
+/// for another.
+pub trait NonInteractive<E: Pairing>: PolyCommit<E> {
+    /// As `open`, but derives the verifier parameters from a transcript over the
+    /// commitments `c` to the polynomials in `poly`, along with `z` and `v`.
+    fn open_non_interactive(&self, pk: &Self::PK, c: &Self::Commitment, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<Self::Proof, PolyCommitError>;
+
+    /// As `verify`, but derives the verifier parameters in the same way as
+    /// `open_non_interactive`.
+    fn verify_non_interactive(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<bool, PolyCommitError>;
+}
+
+/// Fails if `v` is empty.
+pub(crate) fn check_non_empty<T>(v: &[T]) -> Result<(), PolyCommitError> {
+    if v.is_empty() {
+        return Err(PolyCommitError::EmptyInput);
+    }
+    Ok(())
+}
+
+/// Fails if `found` elements were given where `expected` are required.
+pub(crate) fn check_len(expected: usize, found: usize) -> Result<(), PolyCommitError> {
+    if expected != found {
+        return Err(PolyCommitError::LengthMismatch { expected, found });
+    }
+    Ok(())
+}
+
+/// Fails if any of the polynomials in `poly` cannot be committed to with a
+/// prover key holding `num_powers` powers.
+pub(crate) fn check_degrees<F: Field>(poly: &[DensePolynomial<F>], num_powers: usize) -> Result<(), PolyCommitError> {
+    if num_powers == 0 {
+        return Err(PolyCommitError::MalformedKey);
+    }
+    match poly.iter().find(|p| p.degree() >= num_powers) {
+        Some(p) => Err(PolyCommitError::DegreeTooLarge { degree: p.degree(), max_degree: num_powers - 1 }),
+        None => Ok(()),
+    }
+}
+
+/// Fails unless `v` holds the values of `num_polys` polynomials at `num_points`
+/// points each.
+pub(crate) fn check_evaluations<F>(num_polys: usize, num_points: usize, v: &[Vec<F>]) -> Result<(), PolyCommitError> {
+    check_len(num_polys, v.len())?;
+    for v_j in v {
+        check_len(num_points, v_j.len())?;
+    }
+    Ok(())
+}
+
+/// Fails if any point in `z` appears more than once.
+pub(crate) fn check_distinct<F: Field>(z: &[F]) -> Result<(), PolyCommitError> {
+    let mut seen = HashSet::with_capacity(z.len());
+    match z.iter().position(|point| !seen.insert(point)) {
+        Some(i) => Err(PolyCommitError::DuplicatePoint(i)),
+        None => Ok(()),
+    }
+}
+
+/// Returns the union `T` of the point sets `S_i` in `sets`, in order of
+/// first appearance, along with `T\S_i` for each of them. Fails if any
+/// `S_i` is empty or contains a point twice.
+pub(crate) fn complements<F: Field>(sets: &[&[F]]) -> Result<(Vec<F>, Vec<Vec<F>>), PolyCommitError> {
+    let mut T = vec![];
+    let mut seen = HashSet::new();
+    for s in sets {
+        check_non_empty(s)?;
+        check_distinct(s)?;
+        T.extend(s.iter().filter(|t| seen.insert(**t)));
+    }
+
+    let rest = sets.iter().map(|s| {
+        if s.len() == T.len() {
+            return vec![];
+        }
+        let s: HashSet<_> = s.iter().collect();
+        T.iter().filter(|t| !s.contains(t)).copied().collect()
+    }).collect();
+
+    Ok((T, rest))
 }
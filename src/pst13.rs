@@ -0,0 +1,203 @@
+use crate::commitment::{to_group, Commitment};
+use crate::poly_commit::{check_len, check_non_empty, PolyCommitError};
+use ark_ff::{Field, UniformRand};
+use ark_poly::{DenseMultilinearExtension, Polynomial};
+use ark_std::{cfg_iter, rand::{CryptoRng, RngCore}, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, ScalarMul, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::rngs::OsRng;
+use std::marker::PhantomData;
+
+/// Struct for implementing the multilinear polynomial commitment scheme of
+/// [this paper](https://eprint.iacr.org/2011/587.pdf) (PST13, or multilinear
+/// KZG), for polynomials in `n` variables given by their evaluations over the
+/// boolean hypercube {0,1}^n as a `DenseMultilinearExtension`.
+///
+/// The SRS holds g_1^{eq(b, t)} for a secret point t in F^n and every b in
+/// {0,1}^n, so a commitment is g_1^{f(t)}. Any f satisfies
+///     f(x) - f(u) = \sum_k (x_k - u_k) q_k(x_{k+1}, .... , x_n),
+/// and so the proof of its value at u consists of the `n` witnesses
+/// g_1^{q_k(t)}, which are checked with one multi-pairing of `n + 1` terms.
+#[derive(Debug)]
+pub struct PST13<E: Pairing> {
+    _phantom: PhantomData<E>,
+}
+
+/// Prover key for polynomials in `n` variables.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultilinearProverKey<E: Pairing> {
+    /// `g1_vecs[k]` corresponds to <g_1^{eq(b, (t_{k+1}, .... , t_n))}> for
+    /// every b in {0,1}^{n-k}, with the bits of the index of b in the order of
+    /// the variables, as in the evaluations of a `DenseMultilinearExtension`.
+    /// `g1_vecs[0]` commits to polynomials in all of the variables, and the
+    /// others to the quotients q_k.
+    pub g1_vecs: Vec<Vec<E::G1Affine>>,
+}
+
+/// Verifier key for polynomials in `n` variables.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultilinearVerifierKey<E: Pairing> {
+    /// Corresponds to g_1
+    pub g1: E::G1,
+    /// Corresponds to g_2
+    pub g2: E::G2,
+    /// Corresponds to <g_2^{t_1}, g_2^{t_2}, .... , g_2^{t_n}>
+    pub g2_t: Vec<E::G2>,
+}
+
+impl<E: Pairing> PST13<E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData
+        }
+    }
+
+    /// Generates the keys for polynomials in `num_vars` variables. The secret
+    /// point and generators are drawn from the operating system's entropy
+    /// source; see `setup_with_rng`.
+    pub fn setup(&self, num_vars: usize) -> (MultilinearProverKey<E>, MultilinearVerifierKey<E>) {
+        self.setup_with_rng(num_vars, &mut OsRng)
+    }
+
+    /// As `setup`, but draws the secret point and generators from `rng`. The
+    /// secret point is discarded when this function returns.
+    pub fn setup_with_rng<R: RngCore + CryptoRng>(&self, num_vars: usize, rng: &mut R) -> (MultilinearProverKey<E>, MultilinearVerifierKey<E>) {
+        let t: Vec<E::ScalarField> = (0..num_vars).map(|_| E::ScalarField::rand(rng)).collect();
+        let g1 = E::G1::rand(rng);
+        let g2 = E::G2::rand(rng);
+
+        // The table of eq(b, (t_{k+1}, .... , t_n)) is built from that of
+        // eq(b, (t_{k+2}, .... , t_n)), as the lowest bit of b is for t_{k+1}.
+        let mut tables = vec![vec![E::ScalarField::ONE]];
+        for t_k in t.iter().rev() {
+            let table = tables.last().unwrap().iter().flat_map(|e| [*e * (E::ScalarField::ONE - t_k), *e * t_k]).collect();
+            tables.push(table);
+        }
+        tables.reverse();
+
+        let pk = MultilinearProverKey { g1_vecs: tables.iter().map(|table| g1.batch_mul(table)).collect() };
+        let vk = MultilinearVerifierKey { g1, g2, g2_t: t.iter().map(|t_k| g2 * t_k).collect() };
+        (pk, vk)
+    }
+
+    /// Commits to the polynomials in `poly`, yields one element of G_1 per
+    /// polynomial. Each polynomial must be in as many variables as `pk`.
+    pub fn commit(&self, pk: &MultilinearProverKey<E>, poly: &[DenseMultilinearExtension<E::ScalarField>]) -> Result<Vec<Commitment<E>>, PolyCommitError> {
+        pk.check()?;
+        check_num_vars(poly, pk.num_vars())?;
+
+        Ok(cfg_iter!(poly).map(|f| Commitment(E::G1::msm_unchecked(&pk.g1_vecs[0], &f.evaluations))).collect())
+    }
+
+    /// Evaluates the polynomials in `poly` at `point`, and returns those
+    /// values in a vector.
+    pub fn evaluate(&self, poly: &[DenseMultilinearExtension<E::ScalarField>], point: &[E::ScalarField]) -> Result<Vec<E::ScalarField>, PolyCommitError> {
+        check_num_vars(poly, point.len())?;
+
+        let point = point.to_vec();
+        Ok(cfg_iter!(poly).map(|f| f.evaluate(&point)).collect())
+    }
+
+    /// Creates the `n` witnesses to the value of each of the polynomials in
+    /// `poly` at `point`. The evaluations of the quotient q_k over the
+    /// hypercube are f_{k-1}(1, b) - f_{k-1}(0, b), where f_{k-1} is f with its
+    /// first k - 1 variables fixed to those of `point`, so all of the quotients
+    /// are found in O(2^n) field operations.
+    pub fn open(&self, pk: &MultilinearProverKey<E>, poly: &[DenseMultilinearExtension<E::ScalarField>], point: &[E::ScalarField]) -> Result<Vec<Vec<E::G1>>, PolyCommitError> {
+        pk.check()?;
+        check_len(pk.num_vars(), point.len())?;
+        check_num_vars(poly, pk.num_vars())?;
+
+        Ok(cfg_iter!(poly).map(|f| {
+            let mut f = f.evaluations.clone();
+            point.iter().zip(&pk.g1_vecs[1..]).map(|(u_k, g1_vec)| {
+                let q: Vec<E::ScalarField> = f.chunks_exact(2).map(|pair| pair[1] - pair[0]).collect();
+                f = f.chunks_exact(2).zip(&q).map(|(pair, q_b)| pair[0] + *u_k * q_b).collect();
+                E::G1::msm_unchecked(g1_vec, &q)
+            }).collect()
+        }).collect())
+    }
+
+    /// Verifies that the proof `p` is valid for the given parameters, and
+    /// returns true if it is, and false if it is not. The checks for each
+    /// polynomial are folded together with scalars drawn from the operating
+    /// system's entropy source; see `verify_with_rng`.
+    pub fn verify(c: &[Commitment<E>], vk: &MultilinearVerifierKey<E>, p: &[Vec<E::G1>], point: &[E::ScalarField], v: &[E::ScalarField]) -> Result<bool, PolyCommitError> {
+        Self::verify_with_rng(c, vk, p, point, v, &mut OsRng)
+    }
+
+    /// As `verify`, but draws the scalars `r` used to batch the checks from
+    /// `rng`. Each opening satisfies
+    ///     e(c_i - v_i g_1, g_2) = \prod_k e(w_ik, g_2^{t_k - u_k}),
+    /// so with one random `r_i` per polynomial, it suffices to check
+    ///     e(\sum r_i (c_i - v_i g_1), g_2) = \prod_k e(\sum r_i w_ik, g_2^{t_k - u_k}),
+    /// which is a single multi-pairing of `n + 1` terms, however many
+    /// polynomials there are.
+    pub fn verify_with_rng<R: RngCore + CryptoRng>(c: &[Commitment<E>], vk: &MultilinearVerifierKey<E>, p: &[Vec<E::G1>], point: &[E::ScalarField], v: &[E::ScalarField], rng: &mut R) -> Result<bool, PolyCommitError> {
+        vk.check()?;
+        check_non_empty(c)?;
+        check_len(vk.g2_t.len(), point.len())?;
+        check_len(c.len(), p.len())?;
+        check_len(c.len(), v.len())?;
+        for p_i in p {
+            check_len(point.len(), p_i.len())?;
+        }
+
+        let r: Vec<E::ScalarField> = c.iter().map(|_| E::ScalarField::rand(rng)).collect();
+        let g_scalar: E::ScalarField = r.iter().zip(v).map(|(r_i, v_i)| *r_i * v_i).sum();
+        let c = E::G1::normalize_batch(&to_group(c));
+        let lhs = E::G1::msm_unchecked(&c, &r) - vk.g1 * g_scalar;
+
+        let mut g1_terms = vec![lhs];
+        let mut g2_terms = vec![vk.g2];
+        for (k, u_k) in point.iter().enumerate() {
+            let w_k: Vec<E::G1> = p.iter().map(|p_i| p_i[k]).collect();
+            g1_terms.push(-E::G1::msm_unchecked(&E::G1::normalize_batch(&w_k), &r));
+            g2_terms.push(vk.g2_t[k] - vk.g2 * u_k);
+        }
+
+        Ok(E::multi_pairing(g1_terms, g2_terms).is_zero())
+    }
+}
+
+impl<E: Pairing> MultilinearProverKey<E> {
+    /// The number of variables of the polynomials which can be committed to
+    /// with this key.
+    pub fn num_vars(&self) -> usize {
+        self.g1_vecs.len().saturating_sub(1)
+    }
+
+    /// Fails unless there is a basis for each number of variables up to that
+    /// of the key, of the right size.
+    fn check(&self) -> Result<(), PolyCommitError> {
+        let n = self.num_vars();
+        if self.g1_vecs.is_empty() || self.g1_vecs.iter().enumerate().any(|(k, g1_vec)| g1_vec.len() != 1 << (n - k)) {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        Ok(())
+    }
+}
+
+impl<E: Pairing> MultilinearVerifierKey<E> {
+    /// Fails if either generator is the identity, in which case every pairing
+    /// check would trivially pass.
+    fn check(&self) -> Result<(), PolyCommitError> {
+        if self.g1.is_zero() || self.g2.is_zero() {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        Ok(())
+    }
+}
+
+/// Fails unless `poly` is non-empty, and every polynomial is in `num_vars`
+/// variables.
+fn check_num_vars<F: Field>(poly: &[DenseMultilinearExtension<F>], num_vars: usize) -> Result<(), PolyCommitError> {
+    check_non_empty(poly)?;
+    for f in poly {
+        check_len(num_vars, f.num_vars)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,327 @@
+use crate::commitment::{to_group, Commitment};
+use crate::poly_commit::{check_degrees, check_distinct, check_evaluations, check_len, check_non_empty, complements, NonInteractive, PolyCommit, PolyCommitError};
+use crate::transcript::Transcript;
+use crate::srs::{Srs, VerifierKey};
+use ark_ff::Field;
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
+use ark_std::{cfg_iter, rand::{CryptoRng, RngCore}, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use std::marker::PhantomData;
+
+use crate::utils::poly::{eval_poly_over_g1, lagrange_evaluate, lagrange_interpolate, vanishing_poly};
+
+/// Domain separator for the transcripts of `NonInteractive` proofs.
+const SHPLONK_DOMAIN: &[u8] = b"kzg-commitments/shplonk/v1";
+
+/// Struct for implementing SHPLONK, the variant of the batched polynomial
+/// commitment scheme of [this paper](https://eprint.iacr.org/2020/081.pdf)
+/// used by halo2. As with DJBA21 the proof is two elements of G_1, but the
+/// verifier does less work:
+///  - polynomials opened on the same set of points are combined before any
+///    evaluations are interpolated, so the verifier evaluates one
+///    interpolating polynomial per distinct set rather than one per polynomial,
+///    and does so directly from the values, without interpolating them;
+///  - the opening is scaled by 1/Z_{T\S_1}(z), saving a scalar multiplication;
+///  - every group element is combined in a single multi-scalar multiplication,
+///    and checked with a single multi-pairing.
+#[derive(Debug)]
+pub struct SHPLONK<E: Pairing> {
+    _phantom: PhantomData<E>,
+}
+
+/// Polynomials opened on the same set of points.
+struct Group<'a, F> {
+    /// The set of points S.
+    set: &'a [F],
+    /// The points T\S of the union T of every set which are not in S.
+    rest: Vec<F>,
+    /// The indices of the polynomials opened on S.
+    members: Vec<usize>,
+}
+
+/// Implementation of batched polynomial commitments for SHPLONK.
+impl<E: Pairing> PolyCommit<E> for SHPLONK<E> {
+    type PK = Srs<E>;
+    type VK = VerifierKey<E>;
+    /// The commitment consists of 1 element of G_1 for each polynomial
+    /// committed to.
+    type Commitment = Vec<Commitment<E>>;
+    /// List of the values of a polynomial at each input point.
+    type Evaluation = Vec<E::ScalarField>;
+    /// The witness for the evaluation of any number of polynomials at any
+    /// number of points is two elements in G_1.
+    type Proof = (E::G1, E::G1);
+    /// The verifier is required to provide 2 randomly uniformly selected
+    /// parameters from Z_p: `y`, which combines the polynomials, and `z`, at
+    /// which the combination is checked.
+    type VerifierParams = (E::ScalarField, E::ScalarField);
+
+    fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Initialises the public key parameters by as described in the paper,
+    /// for polynomials of degree up to `max_deg`.
+    fn setup_with_rng<R: RngCore + CryptoRng>(&mut self, max_deg: usize, rng: &mut R) -> (Self::PK, Self::VK) {
+        let pk = Srs::generate(max_deg, rng);
+        let vk = pk.verifier_key();
+        (pk, vk)
+    }
+
+    /// Derives the keys for polynomials of degree up to `max_deg` from `srs`.
+    fn trim(&mut self, srs: &Srs<E>, max_deg: usize) -> Result<(Self::PK, Self::VK), PolyCommitError> {
+        srs.trim(max_deg)
+    }
+
+    /// Commits to the polynomials in `poly`, yields one element of G_1 per
+    /// polynomial.
+    fn commit(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>]) -> Result<Self::Commitment, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_degrees(poly, pk.g1_vec.len())?;
+
+        Ok(cfg_iter!(poly).map(|p| Commitment(eval_poly_over_g1::<E>(p, &pk.g1_vec))).collect())
+    }
+
+    /// Evaluates the polynomials in `poly` at each of the points in `z`, which
+    /// must be distinct, and returns those values in a vector.
+    fn evaluate(&self, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField]) -> Result<Vec<Self::Evaluation>, PolyCommitError> {
+        check_non_empty(poly)?;
+        check_non_empty(z)?;
+        check_distinct(z)?;
+
+        Ok(cfg_iter!(poly).map(|p| z.iter().map(|point| p.evaluate(point)).collect()).collect())
+    }
+
+    /// Creates a witness to the values of the polynomials in `poly` at all
+    /// the points in `z`. This is the special case of `open_subsets` where
+    /// every polynomial is opened at every point.
+    ///
+    /// It is a logic error for the values in `v` to be incorrect.
+    fn open(&self, pk: &Self::PK, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<Self::Proof, PolyCommitError> {
+        check_evaluations(poly.len(), z.len(), v)?;
+        let poly: Vec<_> = poly.iter().collect();
+        self.open_sets_with(pk, &poly, &vec![z; poly.len()], v, ver_params.0, |_| ver_params.1)
+    }
+
+    /// Verifies that the proof `p` is valid for the given parameters, and returns true
+    /// if it is, and false if it is not. As with `open`, this is the special case of
+    /// `verify_subsets` where every polynomial is opened at every point.
+    fn verify(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation], ver_params: &Self::VerifierParams) -> Result<bool, PolyCommitError> {
+        check_evaluations(c.len(), z.len(), v)?;
+        Self::verify_sets(c, vk, p, &vec![z; c.len()], v, ver_params)
+    }
+}
+
+/// As for DJBA21, `y` is squeezed from a transcript over the verifier key,
+/// commitments, points and evaluations, and then `z` only once `W` has been
+/// absorbed.
+impl<E: Pairing> NonInteractive<E> for SHPLONK<E> {
+    fn open_non_interactive(&self, pk: &Self::PK, c: &Self::Commitment, poly: &[DensePolynomial<E::ScalarField>], z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<Self::Proof, PolyCommitError> {
+        let mut transcript = Self::transcript(&pk.try_verifier_key()?, c, z, v);
+        let y = transcript.challenge(b"y");
+        check_evaluations(poly.len(), z.len(), v)?;
+        let poly: Vec<_> = poly.iter().collect();
+        self.open_sets_with(pk, &poly, &vec![z; poly.len()], v, y, |W| {
+            transcript.append(b"W", W);
+            transcript.challenge(b"z")
+        })
+    }
+
+    fn verify_non_interactive(c: &Self::Commitment, vk: &Self::VK, p: &Self::Proof, z: &[E::ScalarField], v: &[Self::Evaluation]) -> Result<bool, PolyCommitError> {
+        let mut transcript = Self::transcript(vk, c, z, v);
+        let y = transcript.challenge(b"y");
+        transcript.append(b"W", &p.0);
+        let ver_params = (y, transcript.challenge(b"z"));
+        Self::verify(c, vk, p, z, v, &ver_params)
+    }
+}
+
+impl<E: Pairing> SHPLONK<E> {
+    /// Starts the Fiat-Shamir transcript shared by the prover and verifier.
+    fn transcript(vk: &VerifierKey<E>, c: &[Commitment<E>], z: &[E::ScalarField], v: &[Vec<E::ScalarField>]) -> Transcript {
+        let mut transcript = Transcript::new(SHPLONK_DOMAIN);
+        transcript.append(b"g1", &vk.g1);
+        transcript.append(b"g2_1", &vk.g2_1);
+        transcript.append(b"g2_x", &vk.g2_x);
+        transcript.append(b"commitments", c);
+        transcript.append(b"points", z);
+        transcript.append(b"evaluations", v);
+        transcript
+    }
+
+    /// Evaluates each polynomial `f_i` on its own set of points `S_i`, given as
+    /// `(f_i, S_i)` pairs in `queries`, and returns the values in the order of
+    /// the points in each set.
+    pub fn evaluate_subsets(&self, queries: &[(DensePolynomial<E::ScalarField>, Vec<E::ScalarField>)]) -> Result<Vec<Vec<E::ScalarField>>, PolyCommitError> {
+        check_non_empty(queries)?;
+        for (_, s) in queries {
+            check_non_empty(s)?;
+            check_distinct(s)?;
+        }
+
+        Ok(cfg_iter!(queries).map(|(p, s)| s.iter().map(|point| p.evaluate(point)).collect()).collect())
+    }
+
+    /// Creates a witness to the values of each polynomial `f_i` on its own set
+    /// of points `S_i`, given as `(f_i, S_i)` pairs in `queries`, where `v[i]`
+    /// is as returned by `evaluate_subsets`. The proof is two elements of G_1
+    /// regardless of the number of polynomials and points.
+    ///
+    /// It is a logic error for the values in `v` to be incorrect.
+    pub fn open_subsets(&self, pk: &Srs<E>, queries: &[(DensePolynomial<E::ScalarField>, Vec<E::ScalarField>)], v: &[Vec<E::ScalarField>], ver_params: &(E::ScalarField, E::ScalarField)) -> Result<(E::G1, E::G1), PolyCommitError> {
+        let poly: Vec<_> = queries.iter().map(|(p, _)| p).collect();
+        let sets: Vec<_> = queries.iter().map(|(_, s)| s.as_slice()).collect();
+        self.open_sets_with(pk, &poly, &sets, v, ver_params.0, |_| ver_params.1)
+    }
+
+    /// Verifies the proof `p` created by `open_subsets`, where `c[i]` is the
+    /// commitment to the polynomial opened on the points in `sets[i]`.
+    pub fn verify_subsets(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &(E::G1, E::G1), sets: &[Vec<E::ScalarField>], v: &[Vec<E::ScalarField>], ver_params: &(E::ScalarField, E::ScalarField)) -> Result<bool, PolyCommitError> {
+        let sets: Vec<_> = sets.iter().map(|s| s.as_slice()).collect();
+        Self::verify_sets(c, vk, p, &sets, v, ver_params)
+    }
+
+    /// Numbering the distinct sets S_1, ..., S_k, with T their union, the
+    /// polynomials f_i opened on each S_j are combined with powers of `y`
+    /// into q_j = \sum y^i f_i, whose values on S_j are interpolated by r_j.
+    /// The prover sends
+    ///     W = g^{h(a)}, h = \sum Z_{T\S_j} (q_j - r_j) / Z_T,
+    /// and, once `z` is chosen by `choose_z` after seeing `W`,
+    ///     W' = g^{L(a) / (a - z)},
+    ///     L = \sum Z_{T\S_j}(z) / Z_{T\S_1}(z) (q_j - r_j(z)) - Z_T(z) / Z_{T\S_1}(z) h,
+    /// where L(z) = 0.
+    fn open_sets_with(&self, pk: &Srs<E>, poly: &[&DensePolynomial<E::ScalarField>], sets: &[&[E::ScalarField]], v: &[Vec<E::ScalarField>], y: E::ScalarField, choose_z: impl FnOnce(&E::G1) -> E::ScalarField) -> Result<(E::G1, E::G1), PolyCommitError> {
+        check_non_empty(poly)?;
+        check_len(poly.len(), sets.len())?;
+        let (T, groups) = Self::group(sets)?;
+        Self::check_set_evaluations(sets, v)?;
+        for p in poly {
+            check_degrees(std::slice::from_ref(*p), pk.g1_vec.len())?;
+        }
+
+        let powers = Self::powers(y, poly.len());
+        let combined = groups.iter().map(|g| {
+            let mut q = DensePolynomial::zero();
+            for &i in &g.members {
+                q += (powers[i], poly[i]);
+            }
+            let r = lagrange_interpolate::<E>(&Self::combine_values(g, v, &powers))?;
+            Ok((q, r))
+        }).collect::<Result<Vec<_>, PolyCommitError>>()?;
+
+        let mut f = DensePolynomial::zero();
+        for (g, (q, r)) in groups.iter().zip(&combined) {
+            f += &(&vanishing_poly::<E>(&g.rest) * &(q - r));
+        }
+        let zt = vanishing_poly::<E>(&T);
+        let h = &f / &zt;
+
+        let W = eval_poly_over_g1::<E>(&h, &pk.g1_vec);
+
+        let z = choose_z(&W);
+        let z1_inv = Self::vanish_at(&groups[0].rest, z).inverse().ok_or(PolyCommitError::NonInvertible)?;
+
+        let mut L = &h * (-zt.evaluate(&z) * z1_inv);
+        let mut constant = E::ScalarField::zero();
+        for (g, (q, r)) in groups.iter().zip(&combined) {
+            let scale = Self::vanish_at(&g.rest, z) * z1_inv;
+            L += (scale, q);
+            constant += scale * r.evaluate(&z);
+        }
+        L -= &DensePolynomial::from_coefficients_vec(vec![constant]);
+
+        let L = &L / &DensePolynomial::from_coefficients_slice(&[-z, E::ScalarField::ONE]);
+        let Wp = eval_poly_over_g1::<E>(&L, &pk.g1_vec);
+
+        Ok((W, Wp))
+    }
+
+    /// With `F` the commitment to `L` (see `open_sets_with`) apart from its
+    /// constant term,
+    ///     F = \sum Z_{T\S_j}(z) / Z_{T\S_1}(z) (\sum y^i c_i - r_j(z) g) - Z_T(z) / Z_{T\S_1}(z) W,
+    /// this checks e(F + z W', g2_1) = e(W', g2_x), computing the left hand
+    /// argument with one multi-scalar multiplication.
+    fn verify_sets(c: &[Commitment<E>], vk: &VerifierKey<E>, p: &(E::G1, E::G1), sets: &[&[E::ScalarField]], v: &[Vec<E::ScalarField>], ver_params: &(E::ScalarField, E::ScalarField)) -> Result<bool, PolyCommitError> {
+        vk.check()?;
+        check_non_empty(c)?;
+        check_len(c.len(), sets.len())?;
+        let (T, groups) = Self::group(sets)?;
+        Self::check_set_evaluations(sets, v)?;
+
+        let (y, z) = *ver_params;
+        let (W, Wp) = *p;
+        let powers = Self::powers(y, c.len());
+        let z1_inv = Self::vanish_at(&groups[0].rest, z).inverse().ok_or(PolyCommitError::NonInvertible)?;
+
+        // The scalars multiplying each c_i, then g, W and W'.
+        let mut scalars = vec![E::ScalarField::zero(); c.len()];
+        let mut g_scalar = E::ScalarField::zero();
+        for g in &groups {
+            let scale = Self::vanish_at(&g.rest, z) * z1_inv;
+            for &i in &g.members {
+                scalars[i] = scale * powers[i];
+            }
+            g_scalar -= scale * lagrange_evaluate::<E>(&Self::combine_values(g, v, &powers), z)?;
+        }
+        scalars.extend([g_scalar, -Self::vanish_at(&T, z) * z1_inv, z]);
+
+        let mut bases = to_group(c);
+        bases.extend([vk.g1, W, Wp]);
+        let lhs = E::G1::msm_unchecked(&E::G1::normalize_batch(&bases), &scalars);
+
+        Ok(E::multi_pairing([lhs, -Wp], [vk.g2_1, vk.g2_x]).is_zero())
+    }
+
+    /// Groups the indices of `sets` by their set of points, in order of first
+    /// appearance, and returns the union `T` of the sets along with the groups.
+    /// Sets are only grouped together if their points are in the same order.
+    fn group<'a>(sets: &[&'a [E::ScalarField]]) -> Result<(Vec<E::ScalarField>, Vec<Group<'a, E::ScalarField>>), PolyCommitError> {
+        let mut groups: Vec<Group<'a, E::ScalarField>> = vec![];
+        for (i, s) in sets.iter().enumerate() {
+            match groups.iter_mut().find(|g| g.set == *s) {
+                Some(g) => g.members.push(i),
+                None => groups.push(Group { set: s, rest: vec![], members: vec![i] }),
+            }
+        }
+
+        let distinct: Vec<_> = groups.iter().map(|g| g.set).collect();
+        let (T, rest) = complements(&distinct)?;
+        for (g, rest) in groups.iter_mut().zip(rest) {
+            g.rest = rest;
+        }
+        Ok((T, groups))
+    }
+
+    /// Fails unless there is one value per polynomial and point in its set.
+    /// Where every polynomial is opened on the same points, this is
+    /// `check_evaluations`.
+    fn check_set_evaluations(sets: &[&[E::ScalarField]], v: &[Vec<E::ScalarField>]) -> Result<(), PolyCommitError> {
+        check_len(sets.len(), v.len())?;
+        for (s, v_i) in sets.iter().zip(v) {
+            check_len(s.len(), v_i.len())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the values of q_j = \sum y^i f_i on the points of `g`.
+    fn combine_values(g: &Group<E::ScalarField>, v: &[Vec<E::ScalarField>], powers: &[E::ScalarField]) -> Vec<(E::ScalarField, E::ScalarField)> {
+        g.set.iter().enumerate().map(|(j, t)| {
+            (*t, g.members.iter().map(|&i| powers[i] * v[i][j]).sum())
+        }).collect()
+    }
+
+    /// Returns <1, y, y^2, ... , y^{n-1}>.
+    fn powers(y: E::ScalarField, n: usize) -> Vec<E::ScalarField> {
+        std::iter::successors(Some(E::ScalarField::ONE), |p| Some(*p * y)).take(n).collect()
+    }
+
+    /// Evaluates the vanishing polynomial of `points` at `z`.
+    fn vanish_at(points: &[E::ScalarField], z: E::ScalarField) -> E::ScalarField {
+        points.iter().map(|t| z - t).product()
+    }
+}
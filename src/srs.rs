@@ -0,0 +1,175 @@
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, ScalarMul, VariableBaseMSM};
+use ark_ff::{Field, UniformRand, Zero};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use rand::rngs::OsRng;
+use std::ops::Mul;
+use crate::poly_commit::PolyCommitError;
+
+/// Structured reference string (powers of tau) shared by all of the schemes in
+/// this crate, so that one generated, imported or ceremony-produced setup may
+/// be used with any of them.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Srs<E: Pairing> {
+    /// Corresponds to <g_1, g_1^a, g_1^{a^2}, .... , g_1^{a^t}>, kept in
+    /// affine form as the bases of multi-scalar multiplications.
+    pub g1_vec: Vec<E::G1Affine>,
+    /// Corresponds to g_2
+    pub g2_1: E::G2,
+    /// Corresponds to g_2^a
+    pub g2_x: E::G2,
+}
+
+/// The part of the SRS needed to verify proofs. Unlike the SRS itself, this is
+/// of constant size, regardless of the maximum degree of the polynomials.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifierKey<E: Pairing> {
+    /// Corresponds to g_1
+    pub g1: E::G1,
+    /// Corresponds to g_2
+    pub g2_1: E::G2,
+    /// Corresponds to g_2^a
+    pub g2_x: E::G2,
+}
+
+/// The SRS in the Lagrange basis of a domain of roots of unity, with which
+/// polynomials given by their evaluations over the domain can be committed to
+/// and opened without first interpolating them; see `KZG10::commit_evaluations`.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LagrangeSrs<E: Pairing> {
+    /// Corresponds to <g_1^{L_0(a)}, g_1^{L_1(a)}, .... , g_1^{L_{n-1}(a)}>,
+    /// where L_i is the Lagrange polynomial which is 1 at the ith element of
+    /// `domain` and 0 at the others.
+    pub lagrange_vec: Vec<E::G1Affine>,
+    /// The domain of size `n` over which the evaluations are given.
+    pub domain: Radix2EvaluationDomain<E::ScalarField>,
+}
+
+impl<E: Pairing> Srs<E> {
+    /// Generates the SRS for polynomials of degree up to `max_deg` as described
+    /// in the KZG10 paper, drawing the trapdoor `a` and the generators from
+    /// `rng`. The trapdoor is discarded when this function returns.
+    pub fn generate<R: RngCore + CryptoRng>(max_deg: usize, rng: &mut R) -> Self {
+        Self::generate_with_h(max_deg, rng).0
+    }
+
+    /// As `generate`, but also returns <h, h^a, h^{a^2}, .... , h^{a^t}> for a
+    /// second generator `h` of G_1 drawn from `rng`, whose discrete logarithm
+    /// relative to g_1 is also discarded.
+    pub(crate) fn generate_with_h<R: RngCore + CryptoRng>(max_deg: usize, rng: &mut R) -> (Self, Vec<E::G1Affine>) {
+        let sk = E::ScalarField::rand(rng);
+        let g1 = E::G1::rand(rng);
+        let g2 = E::G2::rand(rng);
+        let mut powers = Vec::with_capacity(max_deg + 1);
+        let mut accum = E::ScalarField::ONE;
+        for _ in 0..=max_deg {
+            powers.push(accum);
+            accum *= sk;
+        }
+        let srs = Self { g1_vec: g1.batch_mul(&powers), g2_1: g2, g2_x: g2.mul(sk) };
+        let h = E::G1::rand(rng);
+        (srs, h.batch_mul(&powers))
+    }
+
+    /// The maximum degree of polynomials which can be committed to with this SRS.
+    pub fn max_degree(&self) -> usize {
+        self.g1_vec.len().saturating_sub(1)
+    }
+
+    /// Returns the verifier key corresponding to this SRS.
+    pub fn verifier_key(&self) -> VerifierKey<E> {
+        VerifierKey { g1: self.g1_vec[0].into_group(), g2_1: self.g2_1, g2_x: self.g2_x }
+    }
+
+    /// As `verifier_key`, but fails rather than panicking if the SRS is empty.
+    pub(crate) fn try_verifier_key(&self) -> Result<VerifierKey<E>, PolyCommitError> {
+        if self.g1_vec.is_empty() {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        Ok(self.verifier_key())
+    }
+
+    /// Derives from this SRS a prover key for polynomials of degree up to
+    /// `max_deg`, which only holds the first `max_deg + 1` powers, along with
+    /// the verifier key. Fails if this SRS does not support `max_deg`.
+    pub fn trim(&self, max_deg: usize) -> Result<(Srs<E>, VerifierKey<E>), PolyCommitError> {
+        if self.g1_vec.is_empty() {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        if max_deg > self.max_degree() {
+            return Err(PolyCommitError::DegreeTooLarge { degree: max_deg, max_degree: self.max_degree() });
+        }
+        let pk = Self { g1_vec: self.g1_vec[..=max_deg].to_vec(), g2_1: self.g2_1, g2_x: self.g2_x };
+        Ok((pk, self.verifier_key()))
+    }
+
+    /// Derives the Lagrange basis of this SRS over the domain of the smallest
+    /// power of 2 at least `size`, for polynomials of degree less than that
+    /// power. As L_i(x) = 1/n \sum_j w^{-ij} x^j for the nth root of unity w,
+    /// the basis is the inverse FFT of the first `n` powers, which is computed
+    /// in G_1 with O(n log n) group operations. Fails if `size` is zero, or
+    /// if this SRS has fewer than `n` powers.
+    pub fn lagrange_basis(&self, size: usize) -> Result<LagrangeSrs<E>, PolyCommitError> {
+        if self.g1_vec.is_empty() {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        if size == 0 {
+            return Err(PolyCommitError::EmptyInput);
+        }
+        let domain = Radix2EvaluationDomain::new(size)
+            .ok_or_else(|| PolyCommitError::DegreeTooLarge { degree: size - 1, max_degree: self.max_degree() })?;
+        if domain.size() > self.g1_vec.len() {
+            return Err(PolyCommitError::DegreeTooLarge { degree: domain.size() - 1, max_degree: self.max_degree() });
+        }
+        let powers: Vec<E::G1> = self.g1_vec[..domain.size()].iter().map(|g| g.into_group()).collect();
+        Ok(LagrangeSrs { lagrange_vec: E::G1::normalize_batch(&domain.ifft(&powers)), domain })
+    }
+
+    /// Checks with pairings that `g1_vec` really does consist of successive
+    /// powers of the secret relating `g2_1` and `g2_x`. Returns true if it
+    /// does, and false if it does not. See `verify_powers`.
+    pub fn is_well_formed(&self) -> bool {
+        verify_powers::<E>(&self.g1_vec, &self.g2_1, &self.g2_x)
+    }
+}
+
+impl<E: Pairing> VerifierKey<E> {
+    /// Fails if any of the generators is the identity, in which case every
+    /// pairing check would trivially pass.
+    pub(crate) fn check(&self) -> Result<(), PolyCommitError> {
+        if self.g1.is_zero() || self.g2_1.is_zero() || self.g2_x.is_zero() {
+            return Err(PolyCommitError::MalformedKey);
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `g1_vec` is of the form <g_1, g_1^a, g_1^{a^2}, .... , g_1^{a^t}>
+/// for the same `a` as relates `g2_1` and `g2_x = g2_1^a`. Returns true if it is,
+/// and false if it is not. The scalars used to batch the checks are drawn from
+/// the operating system's entropy source; see `verify_powers_with_rng`.
+pub fn verify_powers<E: Pairing>(g1_vec: &[E::G1Affine], g2_1: &E::G2, g2_x: &E::G2) -> bool {
+    verify_powers_with_rng::<E, _>(g1_vec, g2_1, g2_x, &mut OsRng)
+}
+
+/// As `verify_powers`, but draws the batching scalars from `rng`.
+///
+/// Rather than checking e(g_1^{a^{i+1}}, g_2) = e(g_1^{a^i}, g_2^a) for every
+/// `i`, which costs two pairings per element, the checks are folded together
+/// with random scalars r_i into
+///     e(\prod g_1^{r_i a^{i+1}}, g_2) = e(\prod g_1^{r_i a^i}, g_2^a),
+/// which fails with overwhelming probability if any single check fails.
+pub fn verify_powers_with_rng<E: Pairing, R: RngCore + CryptoRng>(g1_vec: &[E::G1Affine], g2_1: &E::G2, g2_x: &E::G2, rng: &mut R) -> bool {
+    if g1_vec.is_empty() || g1_vec[0].is_zero() || g2_1.is_zero() || g2_x.is_zero() {
+        return false;
+    }
+
+    let n = g1_vec.len() - 1;
+    let r: Vec<E::ScalarField> = (0..n).map(|_| E::ScalarField::rand(rng)).collect();
+    let lhs = E::G1::msm_unchecked(&g1_vec[1..], &r);
+    let rhs = E::G1::msm_unchecked(&g1_vec[..n], &r);
+
+    E::multi_pairing([lhs, -rhs], [*g2_1, *g2_x]).is_zero()
+}
@@ -0,0 +1,60 @@
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+
+/// Fiat-Shamir transcript, with which the prover and verifier of an interactive
+/// scheme derive the verifier's challenges from the messages sent so far,
+/// rather than having them supplied by the caller.
+///
+/// Both parties must absorb the same messages in the same order, with the same
+/// labels, to arrive at the same challenges. Every message is length-prefixed,
+/// so distinct sequences of messages cannot produce the same transcript.
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    /// Starts a transcript separated from those of every other protocol by
+    /// `domain`.
+    pub fn new(domain: &[u8]) -> Self {
+        let mut transcript = Self { hasher: Sha256::new() };
+        transcript.append_bytes(b"domain", domain);
+        transcript
+    }
+
+    /// Absorbs `bytes`, under `label`.
+    pub fn append_bytes(&mut self, label: &[u8], bytes: &[u8]) {
+        self.hasher.update((label.len() as u64).to_le_bytes());
+        self.hasher.update(label);
+        self.hasher.update((bytes.len() as u64).to_le_bytes());
+        self.hasher.update(bytes);
+    }
+
+    /// Absorbs the compressed serialization of `value`, under `label`.
+    pub fn append<T: CanonicalSerialize + ?Sized>(&mut self, label: &[u8], value: &T) {
+        let mut bytes = Vec::with_capacity(value.compressed_size());
+        value.serialize_compressed(&mut bytes).expect("serializing to a Vec cannot fail");
+        self.append_bytes(label, &bytes);
+    }
+
+    /// Squeezes a challenge from everything absorbed so far, under `label`.
+    /// The challenge is itself absorbed, so successive challenges differ.
+    ///
+    /// 64 bytes of output are reduced modulo the field order, so that the
+    /// challenge is close to uniform even for fields of around 256 bits.
+    pub fn challenge<F: PrimeField>(&mut self, label: &[u8]) -> F {
+        self.append_bytes(b"challenge", label);
+        let mut bytes = [0u8; 64];
+        for (i, chunk) in bytes.chunks_exact_mut(32).enumerate() {
+            chunk.copy_from_slice(&self.hasher.clone().chain_update([i as u8]).finalize());
+        }
+        self.append_bytes(b"output", &bytes);
+        F::from_le_bytes_mod_order(&bytes)
+    }
+
+    /// Squeezes `n` challenges, as with `challenge`.
+    pub fn challenges<F: PrimeField>(&mut self, label: &[u8], n: usize) -> Vec<F> {
+        (0..n).map(|_| self.challenge(label)).collect()
+    }
+}
@@ -0,0 +1,169 @@
+use crate::srs::Srs;
+use crate::utils::poly::bit_reverse_permutation;
+use ark_bls12_381::{Bls12_381, G1Affine, G2Affine};
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalDeserialize, Compress, Validate};
+use std::fmt;
+use std::path::Path;
+
+/// The output of the Ethereum KZG ceremony (the EIP-4844 trusted setup) over
+/// BLS12-381, as published in `trusted_setup.txt` or `trusted_setup_4096.json`.
+///
+/// Every point has been checked to be a valid, non-identity point in the
+/// prime-order subgroup of its group.
+#[derive(Debug, Clone)]
+pub struct TrustedSetup {
+    /// Corresponds to <g_1, g_1^a, g_1^{a^2}, .... , g_1^{a^{n-1}}>
+    pub g1_monomial: Vec<G1Affine>,
+    /// Corresponds to g_1^{L_i(a)}, where L_i are the Lagrange polynomials over
+    /// the `n`th roots of unity, in bit-reversed order as the consensus specs
+    /// use them. Both file formats list them in natural order.
+    pub g1_lagrange: Vec<G1Affine>,
+    /// Corresponds to <g_2, g_2^a, g_2^{a^2}, ...>
+    pub g2_monomial: Vec<G2Affine>,
+}
+
+/// Errors which can occur while loading a trusted setup.
+#[derive(Debug)]
+pub enum TrustedSetupError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file does not follow the expected layout.
+    Format(String),
+    /// A point is not the compressed encoding of a non-identity point in the
+    /// prime-order subgroup.
+    InvalidPoint(String),
+}
+
+impl fmt::Display for TrustedSetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read trusted setup: {e}"),
+            Self::Format(e) => write!(f, "malformed trusted setup: {e}"),
+            Self::InvalidPoint(e) => write!(f, "invalid point in trusted setup: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TrustedSetupError {}
+
+impl From<std::io::Error> for TrustedSetupError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl TrustedSetup {
+    /// Loads a trusted setup from `path`. Files ending in `.json` are parsed
+    /// with `from_json`, and all others with `from_txt`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TrustedSetupError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&contents)
+        } else {
+            Self::from_txt(&contents)
+        }
+    }
+
+    /// Parses the `trusted_setup.txt` format used by c-kzg: the number of G_1
+    /// points and the number of G_2 points on the first two lines, followed by
+    /// the G_1 Lagrange points, the G_2 monomial points and the G_1 monomial
+    /// points, one hex-encoded compressed point per line. As in the JSON
+    /// format, the Lagrange points are listed in natural order.
+    pub fn from_txt(s: &str) -> Result<Self, TrustedSetupError> {
+        let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+        let n_g1 = parse_count(lines.next(), "G1")?;
+        let n_g2 = parse_count(lines.next(), "G2")?;
+
+        let g1_lagrange = lines.by_ref().take(n_g1).map(parse_g1).collect::<Result<Vec<_>, _>>()?;
+        let g2_monomial = lines.by_ref().take(n_g2).map(parse_g2).collect::<Result<Vec<_>, _>>()?;
+        let g1_monomial = lines.by_ref().take(n_g1).map(parse_g1).collect::<Result<Vec<_>, _>>()?;
+        if lines.next().is_some() {
+            return Err(TrustedSetupError::Format("unexpected trailing lines".into()));
+        }
+        Self::new(g1_monomial, g1_lagrange, g2_monomial, n_g1, n_g2)
+    }
+
+    /// Parses the JSON format used by the consensus specs: an object with the
+    /// fields `g1_monomial`, `g1_lagrange` and `g2_monomial`, each a list of
+    /// `0x`-prefixed hex-encoded compressed points, with the Lagrange points in
+    /// natural order.
+    pub fn from_json(s: &str) -> Result<Self, TrustedSetupError> {
+        let json: serde_json::Value = serde_json::from_str(s).map_err(|e| TrustedSetupError::Format(e.to_string()))?;
+        let field = |name: &str| -> Result<Vec<&str>, TrustedSetupError> {
+            json.get(name)
+                .and_then(|v| v.as_array())
+                .and_then(|v| v.iter().map(|p| p.as_str()).collect::<Option<Vec<_>>>())
+                .ok_or_else(|| TrustedSetupError::Format(format!("expected `{name}` to be a list of strings")))
+        };
+
+        let g1_monomial = field("g1_monomial")?.into_iter().map(parse_g1).collect::<Result<Vec<_>, _>>()?;
+        let g1_lagrange = field("g1_lagrange")?.into_iter().map(parse_g1).collect::<Result<Vec<_>, _>>()?;
+        let g2_monomial = field("g2_monomial")?.into_iter().map(parse_g2).collect::<Result<Vec<_>, _>>()?;
+
+        let (n_g1, n_g2) = (g1_monomial.len(), g2_monomial.len());
+        Self::new(g1_monomial, g1_lagrange, g2_monomial, n_g1, n_g2)
+    }
+
+    /// Checks the number of points, and puts the Lagrange points, which both
+    /// formats list in natural order, in bit-reversed order.
+    fn new(g1_monomial: Vec<G1Affine>, mut g1_lagrange: Vec<G1Affine>, g2_monomial: Vec<G2Affine>, n_g1: usize, n_g2: usize) -> Result<Self, TrustedSetupError> {
+        if g1_monomial.len() != n_g1 || g1_lagrange.len() != n_g1 || g2_monomial.len() != n_g2 {
+            return Err(TrustedSetupError::Format("number of points does not match the declared counts".into()));
+        }
+        if n_g1 == 0 || n_g2 < 2 {
+            return Err(TrustedSetupError::Format("at least one G1 point and two G2 points are required".into()));
+        }
+        if !n_g1.is_power_of_two() {
+            return Err(TrustedSetupError::Format("number of G1 points must be a power of 2".into()));
+        }
+        bit_reverse_permutation(&mut g1_lagrange);
+        Ok(Self { g1_monomial, g1_lagrange, g2_monomial })
+    }
+
+    /// Returns the SRS for polynomials of degree up to `n - 1`, which can be
+    /// used with any of the schemes in this crate.
+    pub fn srs(&self) -> Srs<Bls12_381> {
+        Srs {
+            g1_vec: self.g1_monomial.clone(),
+            g2_1: self.g2_monomial[0].into_group(),
+            g2_x: self.g2_monomial[1].into_group(),
+        }
+    }
+}
+
+fn parse_count(line: Option<&str>, name: &str) -> Result<usize, TrustedSetupError> {
+    line.and_then(|l| l.parse().ok())
+        .ok_or_else(|| TrustedSetupError::Format(format!("expected number of {name} points")))
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, TrustedSetupError> {
+    let s = s.trim();
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(|e| TrustedSetupError::Format(format!("{e} in `{s}`")))
+}
+
+fn parse_g1(s: &str) -> Result<G1Affine, TrustedSetupError> {
+    parse_point(s, 48)
+}
+
+fn parse_g2(s: &str) -> Result<G2Affine, TrustedSetupError> {
+    parse_point(s, 96)
+}
+
+/// Decodes a compressed point of `len` bytes, checking that it lies on the
+/// curve, in the prime-order subgroup, and is not the identity.
+fn parse_point<P: SWCurveConfig>(s: &str, len: usize) -> Result<Affine<P>, TrustedSetupError> {
+    let bytes = decode_hex(s)?;
+    if bytes.len() != len {
+        return Err(TrustedSetupError::Format(format!("expected {len} bytes in `{s}`")));
+    }
+
+    let p = Affine::<P>::deserialize_with_mode(bytes.as_slice(), Compress::Yes, Validate::Yes)
+        .map_err(|e| TrustedSetupError::InvalidPoint(format!("{e} in `{s}`")))?;
+    if p.is_zero() || !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(TrustedSetupError::InvalidPoint(format!("`{s}`")));
+    }
+    Ok(p)
+}
@@ -1,44 +1,116 @@
-use crate::poly_commit::{PolyCommit};
+use crate::poly_commit::{PolyCommit, PolyCommitError};
 use ark_ff::{UniformRand, Field};
 use ark_poly::{univariate::{DenseOrSparsePolynomial, DensePolynomial}, DenseUVPolynomial, Polynomial};
 use ark_std::{test_rng, Zero};
 use ark_ec::pairing::{Pairing};
-use ark_ec::AdditiveGroup;
+use ark_ec::{AdditiveGroup, VariableBaseMSM};
 use std::ops::Mul;
 use std::ops::Neg;
 use std::collections::VecDeque;
 
 /// Utility function for evaluating a polynomial over G_1, in the sense of
 /// g^{p_0 + p_1 a + p_2 a^2 + ... p_t a^t}.
+/// This is computed as a single (Pippenger) multi-scalar multiplication of the
+/// coefficients with the affine powers in `srs`, rather than as `t`
+/// independent scalar multiplications.
 #[inline(always)]
-pub fn eval_poly_over_g1<E: Pairing>(poly: &DensePolynomial<E::ScalarField>, srs: &Vec<E::G1>) -> E::G1 {
-    poly.coeffs().iter().zip(srs.iter()).fold(E::G1::ZERO, |acc, (c, g)| acc + g.mul(c))
+pub fn eval_poly_over_g1<E: Pairing>(poly: &DensePolynomial<E::ScalarField>, srs: &[E::G1Affine]) -> E::G1 {
+    let coeffs = poly.coeffs();
+    let n = coeffs.len().min(srs.len());
+    E::G1::msm_unchecked(&srs[..n], &coeffs[..n])
 }
 
-/// Performs lagrange interpolation for the points given in `points` over 
-/// `E::ScalarField`, and returns the polynomial.
-pub fn lagrange_interpolate<E: Pairing>(points: &[(E::ScalarField, E::ScalarField)]) ->DensePolynomial<E::ScalarField> {
-
-    let mut ret = DensePolynomial::from_coefficients_slice(&[E::ScalarField::ZERO]);
+/// Computes the vanishing polynomial
+///     Z_T := \prod_{t \in T} (X - t)
+/// of the points `T` given in `points`, which is 1 if there are none.
+///
+/// The straightforward means of evaluation (multiplying all (X-t) together)
+/// incurs a runtime cost of O(n^2). It is better to evaluate it with a
+/// subproduct tree:
+///
+/// (X - t_1)    (X - t_2)    (X - t_3)    (X - t_4)    (X - t_5)
+/// (X - t_1)(X - t_2)    (X - t_3)(X - t_4)    (X - t_5)
+/// (X - t_1)(X - t_2)(X - t_3)(X - t_4)    (X - t_5)
+/// (X - t_1)(X - t_2)(X - t_3)(X - t_4)(X - t_5)
+///
+/// This is to say, we put all of the linear polynomials on the base level,
+/// and then we combine neighbours together until we are left with one element.
+/// When a level has an odd number of elements the last one is carried up
+/// unchanged. With FFT-based multiplication this gives O(n log^2(n)) overall.
+pub fn vanishing_poly<E: Pairing>(points: &[E::ScalarField]) -> DensePolynomial<E::ScalarField> {
+    let mut level: Vec<DensePolynomial<E::ScalarField>> = points.iter().map(|t| DensePolynomial::from_coefficients_slice(&[t.neg(), E::ScalarField::ONE])).collect();
+    if level.is_empty() {
+        return DensePolynomial::from_coefficients_slice(&[E::ScalarField::ONE]);
+    }
 
-    let mut ztVec: Vec<DensePolynomial<E::ScalarField>> = points.iter().map(|(x, _)| DensePolynomial::from_coefficients_slice(&[x.neg(), E::ScalarField::ONE])).collect();
-    let mut accum = 2;
-    for i in 0..points.len().ilog2() {
-        for j in 0..(points.len()/accum) {
-            ztVec[j] = ztVec[2*j].clone() * ztVec[2*j+1].clone();
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut pairs = level.chunks_exact(2);
+        for pair in &mut pairs {
+            next.push(&pair[0] * &pair[1]);
+        }
+        if let [last] = pairs.remainder() {
+            next.push(last.clone());
         }
-        accum *= 2;
+        level = next;
     }
 
-    let tot = ztVec[0].clone();
+    level.pop().unwrap()
+}
+
+/// Performs lagrange interpolation for the points given in `points` over 
+/// `E::ScalarField`, and returns the polynomial. Fails if two of the points
+/// share an x-coordinate.
+pub fn lagrange_interpolate<E: Pairing>(points: &[(E::ScalarField, E::ScalarField)]) -> Result<DensePolynomial<E::ScalarField>, PolyCommitError> {
+
+    let mut ret = DensePolynomial::from_coefficients_slice(&[E::ScalarField::ZERO]);
+
+    let x: Vec<E::ScalarField> = points.iter().map(|(x, _)| *x).collect();
+    let tot = vanishing_poly::<E>(&x);
 
     for (x, y) in points {
         let cur = tot.clone() / DensePolynomial::from_coefficients_slice(&[x.neg(), E::ScalarField::ONE]);
 
-        let denom = cur.evaluate(x).inverse().unwrap();
+        let denom = cur.evaluate(x).inverse().ok_or(PolyCommitError::NonInvertible)?;
 
         ret = ret + cur * (denom * *y);
     }
 
-    ret
+    Ok(ret)
+}
+
+/// Evaluates at `x` the polynomial of least degree through the points given in
+/// `points`, without interpolating it, in O(n^2) field operations. Fails if
+/// two of the points share an x-coordinate.
+pub fn lagrange_evaluate<E: Pairing>(points: &[(E::ScalarField, E::ScalarField)], x: E::ScalarField) -> Result<E::ScalarField, PolyCommitError> {
+    // The j-th term is y_j \prod_{m != j} (x - x_m) / (x_j - x_m), with all of the
+    // denominators inverted at once.
+    let mut denoms: Vec<E::ScalarField> = points.iter().enumerate().map(|(j, (x_j, _))| {
+        points.iter().enumerate().filter(|&(m, _)| m != j).map(|(_, (x_m, _))| *x_j - x_m).product()
+    }).collect();
+    if denoms.iter().any(|d| d.is_zero()) {
+        return Err(PolyCommitError::NonInvertible);
+    }
+    ark_ff::batch_inversion(&mut denoms);
+
+    Ok(points.iter().enumerate().zip(denoms).map(|((j, (_, y_j)), d)| {
+        let num: E::ScalarField = points.iter().enumerate().filter(|&(m, _)| m != j).map(|(_, (x_m, _))| x - x_m).product();
+        *y_j * num * d
+    }).sum())
+}
+
+/// Permutes `v` in place so that the element at index `i` moves to the index
+/// whose bits are those of `i` reversed. The length of `v` must be a power of 2.
+pub fn bit_reverse_permutation<T>(v: &mut [T]) {
+    let n = v.len();
+    if n <= 1 {
+        return;
+    }
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            v.swap(i, j);
+        }
+    }
 }
@@ -0,0 +1,79 @@
+mod util;
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::UniformRand;
+use kzg_commitments::ceremony::Ceremony;
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::PolyCommit;
+use util::{point_generator, poly_generator, test_rng};
+
+type G1Affine = <Bls12_381 as ark_ec::pairing::Pairing>::G1Affine;
+
+#[test]
+fn ceremony_test() {
+    let mut rng = test_rng();
+
+    let t = 4;
+    let d = 8;
+
+    let mut ceremony = Ceremony::<Bls12_381>::new(d);
+    assert!(!ceremony.verify());
+
+    for _ in 0..3 {
+        ceremony.contribute(&mut rng);
+        assert!(ceremony.verify());
+    }
+
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+
+    let pk = &ceremony.srs;
+    let vk = pk.verifier_key();
+    let kzg = KZG10::<Bls12_381>::new();
+    let c = kzg.commit(pk, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(pk, &poly, &z, &v, &()).unwrap();
+    assert!(KZG10::verify(&c, &vk, &p, &z, &v, &()).unwrap());
+
+    let ver_params = point_generator(t, &mut rng);
+    let gwc = GWC19::<Bls12_381>::new();
+    let c = gwc.commit(pk, &poly).unwrap();
+    let v = gwc.evaluate(&poly, &z).unwrap();
+    let p = gwc.open(pk, &poly, &z, &v, &ver_params).unwrap();
+    assert!(GWC19::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap());
+
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    let djba = DJBA21::<Bls12_381>::new();
+    let c = djba.commit(pk, &poly).unwrap();
+    let v = djba.evaluate(&poly, &z).unwrap();
+    let p = djba.open(pk, &poly, &z, &v, &ver_params).unwrap();
+    assert!(DJBA21::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap());
+}
+
+#[test]
+fn ceremony_tampered_test() {
+    let mut rng = test_rng();
+
+    let mut ceremony = Ceremony::<Bls12_381>::new(8);
+    ceremony.contribute(&mut rng);
+    ceremony.contribute(&mut rng);
+    assert!(ceremony.verify());
+
+    let mut tampered = ceremony.clone();
+    tampered.srs.g1_vec[3] = G1Affine::rand(&mut rng);
+    assert!(!tampered.verify());
+
+    let mut tampered = ceremony.clone();
+    tampered.contributions[0].pok.1 = Fr::rand(&mut rng);
+    assert!(!tampered.verify());
+
+    let mut tampered = ceremony.clone();
+    tampered.contributions.swap(0, 1);
+    assert!(!tampered.verify());
+
+    let mut tampered = ceremony.clone();
+    tampered.contributions.pop();
+    assert!(!tampered.verify());
+}
@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Fresh directory under the system temporary directory for one test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("kzg-commitments-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs the binary in `dir` and returns its exit code.
+fn run(dir: &Path, args: &[&str]) -> i32 {
+    let output = Command::new(env!("CARGO_BIN_EXE_kzg-commitments"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    output.status.code().unwrap()
+}
+
+#[test]
+fn cli_test() {
+    let dir = temp_dir("round-trip");
+    std::fs::write(dir.join("polys.txt"), "1 2 3\n# comment\n\n4 5 6 7 8\n9\n").unwrap();
+    std::fs::write(dir.join("points.txt"), "10\n20\n").unwrap();
+    std::fs::write(dir.join("other_points.txt"), "11\n20\n").unwrap();
+
+    for curve in ["bls12-381", "bls12-377", "bn254"] {
+        for scheme in ["kzg10", "gwc19", "djba21", "shplonk"] {
+            let args = |rest: &[&'static str]| [&["--scheme", scheme, "--curve", curve], rest].concat();
+
+            assert_eq!(run(&dir, &args(&["setup", "--degree", "8", "--seed", "1", "--pk", "pk", "--vk", "vk"])), 0);
+            assert_eq!(run(&dir, &args(&["commit", "--pk", "pk", "--polys", "polys.txt", "--commitment", "c"])), 0);
+            assert_eq!(run(&dir, &args(&["open", "--pk", "pk", "--polys", "polys.txt", "--points", "points.txt", "--commitment", "c", "--evaluations", "v", "--proof", "p"])), 0);
+            assert_eq!(run(&dir, &args(&["verify", "--vk", "vk", "--commitment", "c", "--points", "points.txt", "--evaluations", "v", "--proof", "p"])), 0);
+            for (kind, file) in [("pk", "pk"), ("vk", "vk"), ("commitment", "c"), ("evaluations", "v"), ("proof", "p")] {
+                assert_eq!(run(&dir, &args(&["inspect", "--kind", kind, file])), 0);
+            }
+
+            // A proof for other points is invalid.
+            assert_eq!(run(&dir, &args(&["verify", "--vk", "vk", "--commitment", "c", "--points", "other_points.txt", "--evaluations", "v", "--proof", "p"])), 1);
+        }
+    }
+}
+
+#[test]
+fn cli_error_test() {
+    let dir = temp_dir("errors");
+    std::fs::write(dir.join("polys.txt"), "1 2 3 4 5\n").unwrap();
+    std::fs::write(dir.join("bad.txt"), "1 two 3\n").unwrap();
+
+    assert_eq!(run(&dir, &["setup", "--degree", "2", "--seed", "1", "--pk", "pk", "--vk", "vk"]), 0);
+
+    // Too high a degree, an unparseable polynomial, a missing file, and a key
+    // for another curve are all errors.
+    assert_eq!(run(&dir, &["commit", "--pk", "pk", "--polys", "polys.txt", "--commitment", "c"]), 2);
+    assert_eq!(run(&dir, &["commit", "--pk", "pk", "--polys", "bad.txt", "--commitment", "c"]), 2);
+    assert_eq!(run(&dir, &["commit", "--pk", "missing", "--polys", "polys.txt", "--commitment", "c"]), 2);
+    assert_eq!(run(&dir, &["--curve", "bn254", "inspect", "--kind", "vk", "vk"]), 2);
+}
@@ -0,0 +1,79 @@
+mod util;
+
+use util::{poly_generator, point_generator, test_rng};
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::UniformRand;
+use ark_poly::univariate::DensePolynomial;
+use ark_std::Zero;
+use kzg_commitments::commitment::Commitment;
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::{NonInteractive, PolyCommit, PolyCommitError};
+use kzg_commitments::shplonk::SHPLONK;
+
+type C = Commitment<Bls12_381>;
+
+fn homomorphism_helper<P: NonInteractive<Bls12_381, Commitment = Vec<C>>>() {
+    let mut rng = test_rng();
+
+    let d = 16;
+    let poly = poly_generator(3, d, &mut rng);
+    let (f, g) = (&poly[0], &poly[1]);
+    let (a, b) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+    let mut pc = P::new();
+    let (pk, vk) = pc.setup_with_rng(d, &mut rng);
+    let c = pc.commit(&pk, &poly).unwrap();
+    let commit = |p: DensePolynomial<Fr>| pc.commit(&pk, &[p]).unwrap()[0];
+
+    // commit(a f + b g) = a commit(f) + b commit(g).
+    let mut h = f * a;
+    h += (b, g);
+    assert_eq!(commit(h.clone()), c[0] * a + c[1] * b);
+    assert_eq!(commit(f - g), c[0] - c[1]);
+    assert_eq!(commit(-f.clone()), -c[0]);
+    assert_eq!(commit(DensePolynomial::zero()), C::zero());
+
+    let coeffs = [a, b, Fr::from(7u64)];
+    let mut combined = DensePolynomial::zero();
+    for (coeff, p) in coeffs.iter().zip(&poly) {
+        combined += (*coeff, p);
+    }
+    let folded = C::linear_combination(&coeffs, &c).unwrap();
+    assert_eq!(commit(combined), folded);
+    assert_eq!(folded, c.iter().zip(&coeffs).map(|(c_i, coeff)| *c_i * *coeff).sum());
+
+    let mut acc = c[0];
+    acc *= a;
+    acc += c[1] * b;
+    acc -= c[2];
+    assert_eq!(acc, C::linear_combination(&[a, b, -Fr::from(1u64)], &c).unwrap());
+
+    // A commitment folded without recommitting opens as one made directly.
+    let z = point_generator(3, &mut rng);
+    let folded = vec![c[0] * a + c[1] * b];
+    let v = pc.evaluate(&[h.clone()], &z).unwrap();
+    let p = pc.open_non_interactive(&pk, &folded, &[h], &z, &v).unwrap();
+    assert!(P::verify_non_interactive(&folded, &vk, &p, &z, &v).unwrap());
+}
+
+#[test]
+fn commitment_homomorphism_test() {
+    homomorphism_helper::<KZG10<Bls12_381>>();
+    homomorphism_helper::<GWC19<Bls12_381>>();
+    homomorphism_helper::<DJBA21<Bls12_381>>();
+    homomorphism_helper::<SHPLONK<Bls12_381>>();
+}
+
+#[test]
+fn commitment_linear_combination_test() {
+    let mut rng = test_rng();
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, _) = kzg.setup_with_rng(8, &mut rng);
+    let c = kzg.commit(&pk, &poly_generator(2, 8, &mut rng)).unwrap();
+
+    assert_eq!(C::linear_combination(&[Fr::from(1u64)], &c), Err(PolyCommitError::LengthMismatch { expected: 2, found: 1 }));
+    assert_eq!(C::linear_combination(&[], &[]), Ok(C::zero()));
+}
@@ -2,10 +2,10 @@ mod util;
 
 use ark_bls12_381::{Bls12_381, Fr};
 use ark_ff::UniformRand;
-use ark_std::test_rng;
-use kzg_commitments::djba21::{DJBA21, DJBA21_PK};
+use kzg_commitments::djba21::DJBA21;
 use kzg_commitments::poly_commit::PolyCommit;
-use util::{point_generator, poly_generator};
+use kzg_commitments::srs::VerifierKey;
+use util::{point_generator, poly_generator, test_rng};
 
 fn djba21_helper(poly_count: usize, poly_deg: usize, point_count: usize) -> bool {
     let mut rng = test_rng();
@@ -18,15 +18,15 @@ fn djba21_helper(poly_count: usize, poly_deg: usize, point_count: usize) -> bool
 
     let mut djba = DJBA21::<Bls12_381>::new();
 
-    let (pk, _) = djba.setup(poly_deg);
+    let (pk, vk) = djba.setup_with_rng(poly_deg, &mut rng);
 
-    let c = djba.commit(&pk, &poly);
+    let c = djba.commit(&pk, &poly).unwrap();
 
-    let v = djba.evaluate(&poly, &z);
+    let v = djba.evaluate(&poly, &z).unwrap();
 
-    let p = djba.open(&pk, &poly, &z, &v, &ver_params);
+    let p = djba.open(&pk, &poly, &z, &v, &ver_params).unwrap();
 
-    DJBA21::verify(&c, &pk, &p, &z, &v, &ver_params)
+    DJBA21::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap()
 }
 
 #[test]
@@ -46,7 +46,22 @@ fn djba21_test() -> Result<(), ()> {
         }
     }
 
-    return Ok(());
+    Ok(())
+}
+
+#[test]
+fn djba21_poly_point_count_test() {
+    // The number of polynomials and the number of points vary independently.
+    for &(poly_count, point_count) in &[(20, 2), (1, 64), (5, 1), (1, 3), (7, 4), (3, 10)] {
+        assert!(djba21_helper(poly_count, 16, point_count), "params: polys: {poly_count}, deg: 16, points: {point_count}");
+    }
+}
+
+#[test]
+fn djba21_odd_point_count_test() {
+    for &(count, deg) in &[(1, 8), (3, 8), (5, 16), (7, 10), (13, 20), (100, 128)] {
+        assert!(djba21_helper(count, deg, count), "params: polys: {count}, deg: {deg}, points: {count}");
+    }
 }
 
 #[test]
@@ -68,43 +83,88 @@ pub fn test() {
     type G1 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G1;
     type G2 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G2;
     
-    let (pk, _) = djba.setup(max_deg);
-    let pk_ = DJBA21_PK::<Bls12_381> {
-        g1: vec![G1::rand(&mut rng); t],
-        g2_one: G2::rand(&mut rng),
+    let (pk, vk) = djba.setup_with_rng(max_deg, &mut rng);
+    let vk_ = VerifierKey::<Bls12_381> {
+        g1: G1::rand(&mut rng),
+        g2_1: G2::rand(&mut rng),
         g2_x: G2::rand(&mut rng)
     };
 
-    let c = djba.commit(&pk, &poly);
-    let c_ = djba.commit(&pk, &poly_);
+    let c = djba.commit(&pk, &poly).unwrap();
+    let c_ = djba.commit(&pk, &poly_).unwrap();
 
-    let v = djba.evaluate(&poly, &z);
-    let v_ = djba.evaluate(&poly_, &z);
+    let v = djba.evaluate(&poly, &z).unwrap();
+    let v_ = djba.evaluate(&poly_, &z).unwrap();
 
     let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
     let ver_params_ = (Fr::rand(&mut rng), Fr::rand(&mut rng));
 
-    let p = djba.open(&pk, &poly, &z, &v, &ver_params);
-    let p_ = djba.open(&pk, &poly, &z_, &v, &ver_params);
+    let p = djba.open(&pk, &poly, &z, &v, &ver_params).unwrap();
+    let p_ = djba.open(&pk, &poly, &z_, &v, &ver_params).unwrap();
 
-    let b = DJBA21::verify(&c, &pk, &p, &z, &v, &ver_params);
-    assert_eq!(b, true);
+    let b = DJBA21::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap();
+    assert!(b);
     
-    let b_ = DJBA21::verify(&c_, &pk, &p, &z, &v, &ver_params);
-    assert_eq!(b_, false);
+    let b_ = DJBA21::verify(&c_, &vk, &p, &z, &v, &ver_params).unwrap();
+    assert!(!b_);
     
-    let b_ = DJBA21::verify(&c, &pk_, &p, &z, &v, &ver_params);
-    assert_eq!(b_, false);
+    let b_ = DJBA21::verify(&c, &vk_, &p, &z, &v, &ver_params).unwrap();
+    assert!(!b_);
+
+    let b_ = DJBA21::verify(&c, &vk, &p_, &z, &v, &ver_params).unwrap();
+    assert!(!b_);
 
-    let b_ = DJBA21::verify(&c, &pk, &p_, &z, &v, &ver_params);
-    assert_eq!(b_, false);
+    let b_ = DJBA21::verify(&c, &vk, &p, &z_, &v, &ver_params).unwrap();
+    assert!(!b_);
 
-    let b_ = DJBA21::verify(&c, &pk, &p, &z_, &v, &ver_params);
-    assert_eq!(b_, false);
+    let b_ = DJBA21::verify(&c, &vk, &p, &z, &v_, &ver_params).unwrap();
+    assert!(!b_);
 
-    let b_ = DJBA21::verify(&c, &pk, &p, &z, &v_, &ver_params);
-    assert_eq!(b_, false);
+    let b_ = DJBA21::verify(&c, &vk, &p, &z, &v, &ver_params_).unwrap();
+    assert!(!b_);
+}
+
+#[test]
+fn djba21_subsets_test() {
+    let mut rng = test_rng();
+
+    let d = 16;
+
+    let mut djba = DJBA21::<Bls12_381>::new();
+    let (pk, vk) = djba.setup_with_rng(d, &mut rng);
+
+    let poly = poly_generator(4, d, &mut rng);
+    let t = point_generator(6, &mut rng);
+
+    // Each polynomial is opened on its own subset of the points, some of
+    // which overlap, and one of which is the whole of T.
+    let sets = vec![
+        vec![t[0], t[1]],
+        vec![t[2]],
+        t.clone(),
+        vec![t[5], t[3], t[1]],
+    ];
+    let queries: Vec<_> = poly.iter().cloned().zip(sets.iter().cloned()).collect();
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
 
-    let b_ = DJBA21::verify(&c, &pk, &p, &z, &v, &ver_params_);
-    assert_eq!(b_, false);
+    let c = djba.commit(&pk, &poly).unwrap();
+    let v = djba.evaluate_subsets(&queries).unwrap();
+    let p = djba.open_subsets(&pk, &queries, &v, &ver_params).unwrap();
+    assert!(DJBA21::verify_subsets(&c, &vk, &p, &sets, &v, &ver_params).unwrap());
+
+    // Claiming a polynomial was opened on a different set fails.
+    let mut sets_ = sets.clone();
+    sets_[1] = vec![t[4]];
+    assert!(!DJBA21::verify_subsets(&c, &vk, &p, &sets_, &v, &ver_params).unwrap());
+
+    // As does a wrong evaluation.
+    let mut v_ = v.clone();
+    v_[3] = poly_generator(1, 2, &mut rng).pop().unwrap();
+    assert!(!DJBA21::verify_subsets(&c, &vk, &p, &sets, &v_, &ver_params).unwrap());
+
+    // Opening every polynomial on the same set agrees with `open`.
+    let queries: Vec<_> = poly.iter().map(|p| (p.clone(), t.clone())).collect();
+    let v = djba.evaluate_subsets(&queries).unwrap();
+    assert_eq!(v, djba.evaluate(&poly, &t).unwrap());
+    assert_eq!(djba.open_subsets(&pk, &queries, &v, &ver_params).unwrap(), djba.open(&pk, &poly, &t, &v, &ver_params).unwrap());
 }
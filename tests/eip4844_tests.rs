@@ -0,0 +1,160 @@
+use kzg_commitments::eip4844::{Eip4844Error, KzgSettings, BYTES_PER_BLOB};
+use kzg_commitments::trusted_setup::TrustedSetup;
+use serde_yaml::Value;
+use std::path::Path;
+use std::sync::OnceLock;
+
+// Test vectors in the format of the consensus specs' `kzg-mainnet` tests, one
+// `data.yaml` per case under `fixtures/eip4844/<function>/<case>/`. They are
+// not the specs' own vectors: their outputs were computed with c-kzg 2.1.8 by
+// the generator in `fixtures/eip4844_generator`, which rebuilds them exactly.
+// A null output means the function must fail.
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/eip4844");
+
+fn settings() -> &'static KzgSettings {
+    static SETTINGS: OnceLock<KzgSettings> = OnceLock::new();
+    SETTINGS.get_or_init(|| {
+        let setup = TrustedSetup::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/trusted_setup_4096.txt")).unwrap();
+        KzgSettings::new(&setup).unwrap()
+    })
+}
+
+/// Runs `test` on the input and output of every case for `function`, and
+/// returns the number of cases.
+fn for_each_case(function: &str, test: impl Fn(&str, &Value, &Value)) -> usize {
+    let mut cases: Vec<_> = std::fs::read_dir(Path::new(FIXTURES).join(function)).unwrap().map(|e| e.unwrap().path()).collect();
+    cases.sort();
+    for case in &cases {
+        let data: Value = serde_yaml::from_str(&std::fs::read_to_string(case.join("data.yaml")).unwrap()).unwrap();
+        test(&case.file_name().unwrap().to_string_lossy(), &data["input"], &data["output"]);
+    }
+    cases.len()
+}
+
+fn bytes(v: &Value) -> Vec<u8> {
+    hex::decode(v.as_str().unwrap().strip_prefix("0x").unwrap()).unwrap()
+}
+
+/// Decodes a fixed-length value, or fails as the specs would if its length is wrong.
+fn fixed<const N: usize>(v: &Value) -> Result<[u8; N], Eip4844Error> {
+    bytes(v).try_into().map_err(|_| Eip4844Error::InvalidPoint)
+}
+
+fn list(v: &Value) -> Vec<Value> {
+    v.as_sequence().unwrap().clone()
+}
+
+#[test]
+fn blob_to_kzg_commitment_test() {
+    let n = for_each_case("blob_to_kzg_commitment", |case, input, output| {
+        let result = settings().blob_to_kzg_commitment(&bytes(&input["blob"]));
+        match output {
+            Value::Null => assert!(result.is_err(), "{case}"),
+            c => assert_eq!(result.unwrap().to_vec(), bytes(c), "{case}"),
+        }
+    });
+    assert_eq!(n, 5);
+}
+
+#[test]
+fn compute_kzg_proof_test() {
+    let n = for_each_case("compute_kzg_proof", |case, input, output| {
+        let result = fixed(&input["z"]).and_then(|z| settings().compute_kzg_proof(&bytes(&input["blob"]), &z));
+        match output {
+            Value::Null => assert!(result.is_err(), "{case}"),
+            out => {
+                let (proof, y) = result.unwrap();
+                assert_eq!(proof.to_vec(), bytes(&out[0]), "{case}");
+                assert_eq!(y.to_vec(), bytes(&out[1]), "{case}");
+            }
+        }
+    });
+    assert_eq!(n, 7);
+}
+
+#[test]
+fn compute_blob_kzg_proof_test() {
+    let n = for_each_case("compute_blob_kzg_proof", |case, input, output| {
+        let result = fixed(&input["commitment"]).and_then(|c| settings().compute_blob_kzg_proof(&bytes(&input["blob"]), &c));
+        match output {
+            Value::Null => assert!(result.is_err(), "{case}"),
+            p => assert_eq!(result.unwrap().to_vec(), bytes(p), "{case}"),
+        }
+    });
+    assert_eq!(n, 5);
+}
+
+#[test]
+fn verify_kzg_proof_test() {
+    let n = for_each_case("verify_kzg_proof", |case, input, output| {
+        let result = (|| settings().verify_kzg_proof(&fixed(&input["commitment"])?, &fixed(&input["z"])?, &fixed(&input["y"])?, &fixed(&input["proof"])?))();
+        match output {
+            Value::Null => assert!(result.is_err(), "{case}"),
+            b => assert_eq!(result.unwrap(), b.as_bool().unwrap(), "{case}"),
+        }
+    });
+    assert_eq!(n, 10);
+}
+
+#[test]
+fn verify_blob_kzg_proof_test() {
+    let n = for_each_case("verify_blob_kzg_proof", |case, input, output| {
+        let result = (|| settings().verify_blob_kzg_proof(&bytes(&input["blob"]), &fixed(&input["commitment"])?, &fixed(&input["proof"])?))();
+        match output {
+            Value::Null => assert!(result.is_err(), "{case}"),
+            b => assert_eq!(result.unwrap(), b.as_bool().unwrap(), "{case}"),
+        }
+    });
+    assert_eq!(n, 6);
+}
+
+#[test]
+fn verify_blob_kzg_proof_batch_test() {
+    let n = for_each_case("verify_blob_kzg_proof_batch", |case, input, output| {
+        let result = (|| {
+            let blobs: Vec<Vec<u8>> = list(&input["blobs"]).iter().map(bytes).collect();
+            let commitments = list(&input["commitments"]).iter().map(fixed).collect::<Result<Vec<_>, _>>()?;
+            let proofs = list(&input["proofs"]).iter().map(fixed).collect::<Result<Vec<_>, _>>()?;
+            settings().verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+        })();
+        match output {
+            Value::Null => assert!(result.is_err(), "{case}"),
+            b => assert_eq!(result.unwrap(), b.as_bool().unwrap(), "{case}"),
+        }
+    });
+    assert_eq!(n, 6);
+}
+
+#[test]
+fn eip4844_errors_test() {
+    let blob = vec![0u8; BYTES_PER_BLOB];
+    let c = settings().blob_to_kzg_commitment(&blob).unwrap();
+
+    assert_eq!(settings().blob_to_kzg_commitment(&blob[1..]), Err(Eip4844Error::InvalidBlobLength(BYTES_PER_BLOB - 1)));
+    assert_eq!(settings().compute_kzg_proof(&blob, &[0xff; 32]), Err(Eip4844Error::NonCanonicalFieldElement));
+    assert_eq!(settings().compute_blob_kzg_proof(&blob, &[0x12; 48]), Err(Eip4844Error::InvalidPoint));
+    assert!(matches!(settings().verify_blob_kzg_proof_batch(&[blob], &[c, c], &[c]), Err(Eip4844Error::PolyCommit(_))));
+
+    let small = TrustedSetup::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/trusted_setup_8.txt")).unwrap();
+    assert!(matches!(KzgSettings::new(&small), Err(Eip4844Error::InvalidSetup)));
+}
+
+// Known answers from the tests of c-kzg 2.1.8 (`src/test/tests.c`), for blobs
+// holding a single field element followed by zeros.
+#[test]
+fn eip4844_c_kzg_known_answer_test() {
+    let blob = |element: &str| {
+        let mut blob = vec![0u8; BYTES_PER_BLOB];
+        blob[..32].copy_from_slice(&hex::decode(element).unwrap());
+        blob
+    };
+
+    // test_blob_to_kzg_commitment__succeeds_expected_commitment
+    let c = settings().blob_to_kzg_commitment(&blob("14629a3a39f7b854e6aa49aa2edb450267eac2c14bb2d4f97a0b81a3f57055ad")).unwrap();
+    assert_eq!(hex::encode(c), "91a5e1c143820d2e7bec38a5404c5145807cb88c0abbbecbcb4bccc83a4b417326e337574cff43303f8a6648ecbee7ac");
+
+    // test_compute_kzg_proof__succeeds_expected_proof
+    let z = hex::decode("03ea4fb841b4f9e01aa917c5e40dbd67efb4b8d4d9052069595f0647feba320d").unwrap().try_into().unwrap();
+    let (proof, _) = settings().compute_kzg_proof(&blob("69386e69dbae0357b399b8d645a57a3062dfbe00bd8e97170b9bdd6bc6168a13"), &z).unwrap();
+    assert_eq!(hex::encode(proof), "b21f8f9b85e52fd9c4a6d4fb4e9a27ebdc5a09c3f5ca17f6bcd85c26f04953b0e6925607aaebed1087e5cc2fe4b2b356");
+}
@@ -0,0 +1,159 @@
+mod util;
+
+use util::{poly_generator, point_generator, test_rng};
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ec::AdditiveGroup;
+use ark_ff::{FftField, Field, UniformRand};
+use ark_poly::{EvaluationDomain, Evaluations, Radix2EvaluationDomain};
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::{PolyCommit, PolyCommitError};
+use kzg_commitments::srs::Srs;
+use kzg_commitments::utils::poly::lagrange_interpolate;
+
+type G1 = <Bls12_381 as ark_ec::pairing::Pairing>::G1;
+
+#[test]
+fn degree_too_large_test() {
+    let mut rng = test_rng();
+
+    let poly = poly_generator(2, 16, &mut rng);
+    let z = point_generator(2, &mut rng);
+    let err = PolyCommitError::DegreeTooLarge { degree: 16, max_degree: 8 };
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, _) = kzg.setup_with_rng(8, &mut rng);
+    assert_eq!(kzg.commit(&pk, &poly), Err(err.clone()));
+    assert_eq!(kzg.open(&pk, &poly, &z, &[], &()), Err(err.clone()));
+
+    let gwc = GWC19::<Bls12_381>::new();
+    assert_eq!(gwc.commit(&pk, &poly), Err(err.clone()));
+
+    let djba = DJBA21::<Bls12_381>::new();
+    assert_eq!(djba.commit(&pk, &poly), Err(err));
+
+    assert!(matches!(kzg.trim(&pk, 16), Err(PolyCommitError::DegreeTooLarge { .. })));
+    assert_eq!(pk.lagrange_basis(12).err(), Some(PolyCommitError::DegreeTooLarge { degree: 15, max_degree: 8 }));
+    assert_eq!(pk.lagrange_basis(0).err(), Some(PolyCommitError::EmptyInput));
+}
+
+#[test]
+fn length_mismatch_test() {
+    let mut rng = test_rng();
+
+    let t = 4;
+    let d = 8;
+
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.setup_with_rng(d, &mut rng);
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+    assert_eq!(KZG10::verify(&c[1..].to_vec(), &vk, &p, &z, &v, &()), Err(PolyCommitError::LengthMismatch { expected: t - 1, found: t }));
+
+    let ver_params = point_generator(t, &mut rng);
+    let gwc = GWC19::<Bls12_381>::new();
+    let v = gwc.evaluate(&poly, &z).unwrap();
+    assert_eq!(gwc.open(&pk, &poly, &z, &v, &ver_params[1..].to_vec()), Err(PolyCommitError::LengthMismatch { expected: t, found: t - 1 }));
+    assert_eq!(gwc.open(&pk, &poly, &z, &v[1..], &ver_params), Err(PolyCommitError::LengthMismatch { expected: t, found: t - 1 }));
+    let p = gwc.open(&pk, &poly, &z, &v, &ver_params).unwrap();
+    assert_eq!(GWC19::verify(&c, &vk, &p[1..].to_vec(), &z, &v, &ver_params), Err(PolyCommitError::LengthMismatch { expected: t, found: t - 1 }));
+
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    let djba = DJBA21::<Bls12_381>::new();
+    let v = djba.evaluate(&poly, &z).unwrap();
+    assert_eq!(djba.open(&pk, &poly, &z, &v[1..], &ver_params), Err(PolyCommitError::LengthMismatch { expected: t, found: t - 1 }));
+}
+
+#[test]
+fn duplicate_point_test() {
+    let mut rng = test_rng();
+
+    let poly = poly_generator(4, 8, &mut rng);
+    let mut z = point_generator(4, &mut rng);
+    z[3] = z[1];
+
+    let mut djba = DJBA21::<Bls12_381>::new();
+    let (pk, vk) = djba.setup_with_rng(8, &mut rng);
+    assert_eq!(djba.evaluate(&poly, &z), Err(PolyCommitError::DuplicatePoint(3)));
+
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    let c = djba.commit(&pk, &poly).unwrap();
+    assert_eq!(djba.open(&pk, &poly, &z, &[], &ver_params), Err(PolyCommitError::DuplicatePoint(3)));
+    assert_eq!(DJBA21::verify(&c, &vk, &(G1::ZERO, G1::ZERO), &z, &[], &ver_params), Err(PolyCommitError::DuplicatePoint(3)));
+}
+
+#[test]
+fn empty_input_test() {
+    let mut rng = test_rng();
+
+    let poly = poly_generator(2, 8, &mut rng);
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.setup_with_rng(8, &mut rng);
+    assert_eq!(kzg.commit(&pk, &[]), Err(PolyCommitError::EmptyInput));
+    assert_eq!(kzg.evaluate(&poly, &[]), Err(PolyCommitError::EmptyInput));
+    assert_eq!(KZG10::verify(&vec![], &vk, &vec![], &[], &[], &()), Err(PolyCommitError::EmptyInput));
+
+    let gwc = GWC19::<Bls12_381>::new();
+    assert_eq!(gwc.evaluate(&[], &[Fr::rand(&mut rng)]), Err(PolyCommitError::EmptyInput));
+
+    let djba = DJBA21::<Bls12_381>::new();
+    assert_eq!(djba.commit(&pk, &[]), Err(PolyCommitError::EmptyInput));
+}
+
+#[test]
+fn malformed_key_test() {
+    let mut rng = test_rng();
+
+    let t = 2;
+    let d = 8;
+
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.setup_with_rng(d, &mut rng);
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+
+    // A verifier key containing the identity is rejected.
+    let mut vk_ = vk.clone();
+    vk_.g2_x = <Bls12_381 as ark_ec::pairing::Pairing>::G2::ZERO;
+    assert_eq!(KZG10::verify(&c, &vk_, &p, &z, &v, &()), Err(PolyCommitError::MalformedKey));
+
+    let empty = Srs::<Bls12_381> { g1_vec: vec![], g2_1: pk.g2_1, g2_x: pk.g2_x };
+    assert_eq!(kzg.commit(&empty, &poly), Err(PolyCommitError::MalformedKey));
+    assert_eq!(empty.trim(0).err(), Some(PolyCommitError::MalformedKey));
+}
+
+#[test]
+fn non_invertible_test() {
+    let x = Fr::rand(&mut test_rng());
+    assert_eq!(lagrange_interpolate::<Bls12_381>(&[(x, Fr::ONE), (x, Fr::ZERO)]), Err(PolyCommitError::NonInvertible));
+}
+
+#[test]
+fn domain_mismatch_test() {
+    let mut rng = test_rng();
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (srs, _) = kzg.setup_with_rng(16, &mut rng);
+    let pk = srs.lagrange_basis(16).unwrap();
+    let z = point_generator(2, &mut rng);
+
+    // Evaluations over a smaller domain, and over a coset of the right one.
+    let small = Radix2EvaluationDomain::<Fr>::new(8).unwrap();
+    let coset = pk.domain.get_coset(Fr::GENERATOR).unwrap();
+    let evals = [Evaluations::from_vec_and_domain(vec![Fr::ONE; 8], small)];
+    assert_eq!(kzg.commit_evaluations(&pk, &evals), Err(PolyCommitError::LengthMismatch { expected: 16, found: 8 }));
+    let evals = [Evaluations::from_vec_and_domain(vec![Fr::ONE; 16], coset)];
+    assert_eq!(kzg.commit_evaluations(&pk, &evals), Err(PolyCommitError::DomainMismatch));
+    assert_eq!(kzg.open_evaluations(&pk, &evals, &z, &[vec![Fr::ONE; 2]]), Err(PolyCommitError::DomainMismatch));
+    assert_eq!(kzg.evaluate_evaluations(&evals, &z), Err(PolyCommitError::DomainMismatch));
+}
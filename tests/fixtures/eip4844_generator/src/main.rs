@@ -0,0 +1,328 @@
+//! Generates the EIP-4844 test vectors under `tests/fixtures/eip4844` with
+//! c-kzg, the reference implementation used by the Ethereum clients:
+//!
+//!     cargo run --release -- ../trusted_setup_4096.txt ../eip4844
+//!
+//! Each case is written as `<function>/<case>/data.yaml`, in the format of the
+//! consensus specs' `kzg-mainnet` tests. A null output means c-kzg rejected
+//! the input.
+
+use ark_bls12_381::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use c_kzg::{Blob, Bytes32, Bytes48, KzgSettings, BYTES_PER_BLOB};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt::Write as _;
+use std::path::Path;
+
+fn hx(b: &[u8]) -> String {
+    format!("'0x{}'", hex::encode(b))
+}
+
+fn write(out: &Path, func: &str, case: &str, input: &[(&str, String)], output: String) {
+    let dir = out.join(func).join(case);
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut s = String::from("input:\n");
+    for (k, v) in input {
+        writeln!(s, "  {k}: {v}").unwrap();
+    }
+    writeln!(s, "output: {output}").unwrap();
+    std::fs::write(dir.join("data.yaml"), s).unwrap();
+}
+
+fn list(v: &[Vec<u8>]) -> String {
+    if v.is_empty() {
+        return "[]".into();
+    }
+    let mut s = String::new();
+    for x in v {
+        write!(s, "\n  - {}", hx(x)).unwrap();
+    }
+    s
+}
+
+fn fr_be(x: Fr) -> Vec<u8> {
+    x.into_bigint().to_bytes_be()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let ts = KzgSettings::load_trusted_setup_file(Path::new(&args[1]), 0).unwrap();
+    let out = Path::new(&args[2]);
+    let mut rng = StdRng::seed_from_u64(4844);
+
+    let mut random = vec![0u8; BYTES_PER_BLOB];
+    for c in random.chunks_mut(32) {
+        rng.fill(c);
+        c[0] %= 0x73;
+    }
+    let zero = vec![0u8; BYTES_PER_BLOB];
+    let mut sparse = vec![0u8; BYTES_PER_BLOB];
+    for i in [0usize, 1, 17, 2048, 4095] {
+        sparse[i * 32 + 31] = (i % 251) as u8 + 1;
+        sparse[i * 32] = 0x12;
+    }
+    let mut non_canonical = sparse.clone();
+    non_canonical[32 * 100..32 * 101].copy_from_slice(&[0xff; 32]);
+    let short = sparse[..BYTES_PER_BLOB - 32].to_vec();
+    let blobs = [("zero", &zero), ("random", &random), ("sparse", &sparse)];
+
+    let blob = |b: &[u8]| Blob::from_bytes(b).ok();
+    let commit = |b: &[u8]| {
+        ts.blob_to_kzg_commitment(&blob(b).unwrap())
+            .unwrap()
+            .to_bytes()
+            .into_inner()
+            .to_vec()
+    };
+
+    let mut z_random = vec![0u8; 32];
+    rng.fill(&mut z_random[..]);
+    z_random[0] %= 0x73;
+    let domain = Radix2EvaluationDomain::<Fr>::new(4096).unwrap();
+    let z_domain = fr_be(domain.element(123));
+    let z_non_canonical = vec![0xffu8; 32];
+    let bad_point = hex::decode("8123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef").unwrap();
+
+    // blob_to_kzg_commitment
+    let bc = |case: &str, b: &[u8]| {
+        let o = blob(b)
+            .and_then(|b| ts.blob_to_kzg_commitment(&b).ok())
+            .map(|c| hx(&c.to_bytes().into_inner()))
+            .unwrap_or("null".into());
+        write(out, "blob_to_kzg_commitment", case, &[("blob", hx(b))], o);
+    };
+    for (n, b) in &blobs {
+        bc(&format!("valid_blob_{n}"), b);
+    }
+    bc("invalid_blob_non_canonical", &non_canonical);
+    bc("invalid_blob_length", &short);
+
+    // compute_kzg_proof
+    let ckp = |case: &str, b: &[u8], z: &[u8]| {
+        let o = match (blob(b), Bytes32::from_bytes(z).ok()) {
+            (Some(b), Some(z)) => ts.compute_kzg_proof(&b, &z).ok().map(|(p, y)| {
+                format!("[{}, {}]", hx(&p.to_bytes().into_inner()), hx(y.as_slice()))
+            }),
+            _ => None,
+        }
+        .unwrap_or("null".into());
+        write(
+            out,
+            "compute_kzg_proof",
+            case,
+            &[("blob", hx(b)), ("z", hx(z))],
+            o,
+        );
+    };
+    for (n, b) in &blobs {
+        ckp(&format!("valid_blob_{n}_z_random"), b, &z_random);
+    }
+    ckp("valid_blob_random_z_in_domain", &random, &z_domain);
+    ckp("valid_blob_sparse_z_zero", &sparse, &[0u8; 32]);
+    ckp("invalid_z_non_canonical", &sparse, &z_non_canonical);
+    ckp("invalid_blob_non_canonical", &non_canonical, &z_random);
+
+    // compute_blob_kzg_proof
+    let cbp = |case: &str, b: &[u8], c: &[u8]| {
+        let o = match (blob(b), Bytes48::from_bytes(c).ok()) {
+            (Some(b), Some(c)) => ts
+                .compute_blob_kzg_proof(&b, &c)
+                .ok()
+                .map(|p| hx(&p.to_bytes().into_inner())),
+            _ => None,
+        }
+        .unwrap_or("null".into());
+        write(
+            out,
+            "compute_blob_kzg_proof",
+            case,
+            &[("blob", hx(b)), ("commitment", hx(c))],
+            o,
+        );
+    };
+    for (n, b) in &blobs {
+        cbp(&format!("valid_blob_{n}"), b, &commit(b));
+    }
+    cbp("invalid_commitment_not_on_curve", &sparse, &bad_point);
+    cbp(
+        "invalid_blob_non_canonical",
+        &non_canonical,
+        &commit(&sparse),
+    );
+
+    // verify_kzg_proof
+    let vkp = |case: &str, c: &[u8], z: &[u8], y: &[u8], p: &[u8]| {
+        let o = match (
+            Bytes48::from_bytes(c),
+            Bytes32::from_bytes(z),
+            Bytes32::from_bytes(y),
+            Bytes48::from_bytes(p),
+        ) {
+            (Ok(c), Ok(z), Ok(y), Ok(p)) => ts
+                .verify_kzg_proof(&c, &z, &y, &p)
+                .ok()
+                .map(|b| b.to_string()),
+            _ => None,
+        }
+        .unwrap_or("null".into());
+        write(
+            out,
+            "verify_kzg_proof",
+            case,
+            &[
+                ("commitment", hx(c)),
+                ("z", hx(z)),
+                ("y", hx(y)),
+                ("proof", hx(p)),
+            ],
+            o,
+        );
+    };
+    let open = |b: &[u8], z: &[u8]| {
+        let (p, y) = ts
+            .compute_kzg_proof(&blob(b).unwrap(), &Bytes32::from_bytes(z).unwrap())
+            .unwrap();
+        (p.to_bytes().into_inner().to_vec(), y.as_slice().to_vec())
+    };
+    let (p, y) = open(&random, &z_random);
+    let c = commit(&random);
+    vkp("verify_correct_proof_random", &c, &z_random, &y, &p);
+    let mut y_ = y.clone();
+    y_[31] ^= 1;
+    vkp("verify_incorrect_y", &c, &z_random, &y_, &p);
+    let (p_other, _) = open(&sparse, &z_random);
+    vkp("verify_incorrect_proof", &c, &z_random, &y, &p_other);
+    let (pd, yd) = open(&random, &z_domain);
+    vkp("verify_correct_proof_in_domain", &c, &z_domain, &yd, &pd);
+    let (pz, yz) = open(&zero, &z_random);
+    vkp(
+        "verify_correct_proof_point_at_infinity",
+        &commit(&zero),
+        &z_random,
+        &yz,
+        &pz,
+    );
+    vkp("invalid_z_non_canonical", &c, &z_non_canonical, &y, &p);
+    vkp(
+        "invalid_y_non_canonical",
+        &c,
+        &z_random,
+        &z_non_canonical,
+        &p,
+    );
+    vkp(
+        "invalid_commitment_not_on_curve",
+        &bad_point,
+        &z_random,
+        &y,
+        &p,
+    );
+    vkp("invalid_proof_not_on_curve", &c, &z_random, &y, &bad_point);
+    vkp("invalid_proof_length", &c, &z_random, &y, &p[..47]);
+
+    // verify_blob_kzg_proof
+    let vbp = |case: &str, b: &[u8], c: &[u8], p: &[u8]| {
+        let o = match (blob(b), Bytes48::from_bytes(c), Bytes48::from_bytes(p)) {
+            (Some(b), Ok(c), Ok(p)) => ts
+                .verify_blob_kzg_proof(&b, &c, &p)
+                .ok()
+                .map(|b| b.to_string()),
+            _ => None,
+        }
+        .unwrap_or("null".into());
+        write(
+            out,
+            "verify_blob_kzg_proof",
+            case,
+            &[("blob", hx(b)), ("commitment", hx(c)), ("proof", hx(p))],
+            o,
+        );
+    };
+    let blob_proof = |b: &[u8]| {
+        ts.compute_blob_kzg_proof(&blob(b).unwrap(), &Bytes48::from_bytes(&commit(b)).unwrap())
+            .unwrap()
+            .to_bytes()
+            .into_inner()
+            .to_vec()
+    };
+    for (n, b) in &blobs {
+        vbp(
+            &format!("verify_correct_proof_{n}"),
+            b,
+            &commit(b),
+            &blob_proof(b),
+        );
+    }
+    vbp(
+        "verify_incorrect_proof",
+        &sparse,
+        &commit(&sparse),
+        &blob_proof(&zero),
+    );
+    vbp(
+        "invalid_commitment_not_on_curve",
+        &sparse,
+        &bad_point,
+        &blob_proof(&sparse),
+    );
+    vbp(
+        "invalid_blob_non_canonical",
+        &non_canonical,
+        &commit(&sparse),
+        &blob_proof(&sparse),
+    );
+
+    // verify_blob_kzg_proof_batch
+    let vbb = |case: &str, bs: &[&Vec<u8>], cs: &[Vec<u8>], ps: &[Vec<u8>]| {
+        let b: Option<Vec<Blob>> = bs.iter().map(|b| blob(b)).collect();
+        let c: Result<Vec<Bytes48>, _> = cs.iter().map(|c| Bytes48::from_bytes(c)).collect();
+        let p: Result<Vec<Bytes48>, _> = ps.iter().map(|p| Bytes48::from_bytes(p)).collect();
+        let o = match (b, c, p) {
+            (Some(b), Ok(c), Ok(p)) => ts
+                .verify_blob_kzg_proof_batch(&b, &c, &p)
+                .ok()
+                .map(|b| b.to_string()),
+            _ => None,
+        }
+        .unwrap_or("null".into());
+        let bl: Vec<Vec<u8>> = bs.iter().map(|b| b.to_vec()).collect();
+        write(
+            out,
+            "verify_blob_kzg_proof_batch",
+            case,
+            &[
+                ("blobs", list(&bl)),
+                ("commitments", list(cs)),
+                ("proofs", list(ps)),
+            ],
+            o,
+        );
+    };
+    let all: Vec<&Vec<u8>> = blobs.iter().map(|(_, b)| *b).collect();
+    let cs: Vec<Vec<u8>> = all.iter().map(|b| commit(b)).collect();
+    let ps: Vec<Vec<u8>> = all.iter().map(|b| blob_proof(b)).collect();
+    vbb("verify_blob_kzg_proof_batch_length_0", &[], &[], &[]);
+    vbb(
+        "verify_blob_kzg_proof_batch_length_1",
+        &all[1..2],
+        &cs[1..2],
+        &ps[1..2],
+    );
+    vbb("verify_blob_kzg_proof_batch_length_3", &all, &cs, &ps);
+    let mut ps_ = ps.clone();
+    ps_.swap(1, 2);
+    vbb("verify_incorrect_proof", &all, &cs, &ps_);
+    let light = [all[0], all[2]];
+    let (cl, pl) = (
+        vec![cs[0].clone(), cs[2].clone()],
+        vec![ps[0].clone(), ps[2].clone()],
+    );
+    vbb(
+        "invalid_commitment_not_on_curve",
+        &light,
+        &[cl[0].clone(), bad_point.clone()],
+        &pl,
+    );
+    vbb("invalid_length_mismatch", &light, &cl[..1], &pl);
+}
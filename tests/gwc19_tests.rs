@@ -2,10 +2,10 @@ mod util;
 
 use ark_bls12_381::Bls12_381;
 use ark_ff::UniformRand;
-use ark_std::test_rng;
-use kzg_commitments::gwc19::{GWC19, GWC_PK};
+use kzg_commitments::gwc19::GWC19;
 use kzg_commitments::poly_commit::PolyCommit;
-use util::{point_generator, poly_generator};
+use kzg_commitments::srs::VerifierKey;
+use util::{point_generator, poly_generator, test_rng};
 
 fn gwc19_helper(poly_count: usize, poly_deg: usize, point_count: usize) -> bool {
     let mut rng = test_rng();
@@ -14,19 +14,19 @@ fn gwc19_helper(poly_count: usize, poly_deg: usize, point_count: usize) -> bool
 
     let z = point_generator(point_count, &mut rng);
     
-    let ver_params = point_generator(poly_count, &mut rng);
+    let ver_params = point_generator(point_count, &mut rng);
 
     let mut kzg = GWC19::<Bls12_381>::new();
 
-    let (pk, _) = kzg.setup(poly_deg);
+    let (pk, vk) = kzg.setup_with_rng(poly_deg, &mut rng);
 
-    let c = kzg.commit(&pk, &poly);
+    let c = kzg.commit(&pk, &poly).unwrap();
 
-    let v = kzg.evaluate(&poly, &z);
+    let v = kzg.evaluate(&poly, &z).unwrap();
 
-    let p = kzg.open(&pk, &poly, &z, &v, &ver_params);
+    let p = kzg.open(&pk, &poly, &z, &v, &ver_params).unwrap();
 
-    GWC19::verify(&c, &pk, &p, &z, &v, &ver_params)
+    GWC19::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap()
 }
 
 #[test]
@@ -46,7 +46,15 @@ fn gwc19_test() -> Result<(), ()> {
         }
     }
 
-    return Ok(());
+    Ok(())
+}
+
+#[test]
+fn gwc19_poly_point_count_test() {
+    // The number of polynomials and the number of points vary independently.
+    for &(poly_count, point_count) in &[(20, 2), (1, 64), (5, 1), (1, 3), (7, 4), (3, 10)] {
+        assert!(gwc19_helper(poly_count, 16, point_count), "params: polys: {poly_count}, deg: 16, points: {point_count}");
+    }
 }
 
 #[test]
@@ -71,42 +79,48 @@ pub fn basic_gwc19_test() {
     type G1 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G1;
     type G2 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G2;
 
-    let (pk, _) = kzg.setup(max_deg);
-    let pk_ = GWC_PK::<Bls12_381> {
-        g1_vec: vec![G1::rand(&mut rng); t],
+    let (pk, vk) = kzg.setup_with_rng(max_deg, &mut rng);
+    let vk_ = VerifierKey::<Bls12_381> {
+        g1: G1::rand(&mut rng),
         g2_1: G2::rand(&mut rng),
         g2_x: G2::rand(&mut rng),
     };
 
-    let c = kzg.commit(&pk, &poly);
-    let c_ = kzg.commit(&pk, &poly_);
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let c_ = kzg.commit(&pk, &poly_).unwrap();
 
-    let v = kzg.evaluate(&poly, &z);
-    let v_ = kzg.evaluate(&poly_, &z);
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let v_ = kzg.evaluate(&poly_, &z).unwrap();
 
-    let p = kzg.open(&pk, &poly, &z, &v, &ver_params);
-    let p_ = kzg.open(&pk, &poly, &z_, &v, &ver_params);
+    let p = kzg.open(&pk, &poly, &z, &v, &ver_params).unwrap();
+    let p_ = kzg.open(&pk, &poly, &z_, &v, &ver_params).unwrap();
 
     let mut b;
     
-    b = GWC19::verify(&c, &pk, &p, &z, &v, &ver_params);
-    assert_eq!(b, true);
+    b = GWC19::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap();
+    assert!(b);
+
+    b = GWC19::verify_with_rng(&c, &vk, &p, &z, &v, &ver_params, &mut rng).unwrap();
+    assert!(b);
+
+    b = GWC19::verify_with_rng(&c, &vk, &p_, &z, &v, &ver_params, &mut rng).unwrap();
+    assert!(!b);
     
-    b = GWC19::verify(&c_, &pk, &p, &z, &v, &ver_params);
-    assert_eq!(b, false);
+    b = GWC19::verify(&c_, &vk, &p, &z, &v, &ver_params).unwrap();
+    assert!(!b);
     
-    b = GWC19::verify(&c, &pk_, &p, &z, &v, &ver_params);
-    assert_eq!(b, false);
+    b = GWC19::verify(&c, &vk_, &p, &z, &v, &ver_params).unwrap();
+    assert!(!b);
     
-    b = GWC19::verify(&c, &pk, &p_, &z, &v, &ver_params);
-    assert_eq!(b, false);
+    b = GWC19::verify(&c, &vk, &p_, &z, &v, &ver_params).unwrap();
+    assert!(!b);
     
-    b = GWC19::verify(&c, &pk, &p, &z_, &v, &ver_params);
-    assert_eq!(b, false);
+    b = GWC19::verify(&c, &vk, &p, &z_, &v, &ver_params).unwrap();
+    assert!(!b);
     
-    b = GWC19::verify(&c, &pk, &p, &z, &v_, &ver_params);
-    assert_eq!(b, false);
+    b = GWC19::verify(&c, &vk, &p, &z, &v_, &ver_params).unwrap();
+    assert!(!b);
     
-    b = GWC19::verify(&c, &pk, &p, &z, &v, &ver_params_);
-    assert_eq!(b, false);
+    b = GWC19::verify(&c, &vk, &p, &z, &v, &ver_params_).unwrap();
+    assert!(!b);
 }
@@ -1,11 +1,15 @@
 mod util;
 
-use util::{poly_generator, point_generator};
-use ark_bls12_381::Bls12_381;
+use util::{poly_generator, point_generator, test_rng};
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::{FftField, Field};
 use ark_ff::UniformRand;
-use ark_std::test_rng;
-use kzg_commitments::kzg10::{KZG10, KZG_PK};
-use kzg_commitments::poly_commit::PolyCommit;
+use ark_poly::{EvaluationDomain, Evaluations, Radix2EvaluationDomain};
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::{PolyCommit, PolyCommitError};
+use kzg_commitments::srs::VerifierKey;
+
+type G1 = <Bls12_381 as ark_ec::pairing::Pairing>::G1;
 
 fn kzg10_helper(poly_count: usize, poly_deg: usize, point_count: usize) -> bool {
     let mut rng = test_rng();
@@ -18,15 +22,15 @@ fn kzg10_helper(poly_count: usize, poly_deg: usize, point_count: usize) -> bool
 
     let mut kzg = KZG10::<Bls12_381>::new();
 
-    let (pk, _) = kzg.setup(poly_deg);
+    let (pk, vk) = kzg.setup_with_rng(poly_deg, &mut rng);
 
-    let c = kzg.commit(&pk, &poly);
+    let c = kzg.commit(&pk, &poly).unwrap();
 
-    let v = kzg.evaluate(&poly, &z);
+    let v = kzg.evaluate(&poly, &z).unwrap();
 
-    let p = kzg.open(&pk, &poly, &z, &v, &ver_params);
+    let p = kzg.open(&pk, &poly, &z, &v, &ver_params).unwrap();
 
-    KZG10::verify(&c, &pk, &p, &z, &v, &ver_params)
+    KZG10::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap()
 }
 
 #[test]
@@ -46,7 +50,15 @@ fn kzg10_test() -> Result<(), ()> {
         }
     }
 
-    return Ok(());
+    Ok(())
+}
+
+#[test]
+fn kzg10_poly_point_count_test() {
+    // The number of polynomials and the number of points vary independently.
+    for &(poly_count, point_count) in &[(20, 2), (1, 64), (5, 1), (1, 3), (7, 4), (3, 10)] {
+        assert!(kzg10_helper(poly_count, 16, point_count), "params: polys: {poly_count}, deg: 16, points: {point_count}");
+    }
 }
 
 #[test]
@@ -68,37 +80,228 @@ pub fn basic_kzg10_test() {
     type G1 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G1;
     type G2 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G2;
     
-    let (pk, _) = kzg.setup(max_deg);
-    let pk_ = KZG_PK::<Bls12_381> {
-        g1_vec: vec![G1::rand(&mut rng); t],
+    let (pk, vk) = kzg.setup_with_rng(max_deg, &mut rng);
+    let vk_ = VerifierKey::<Bls12_381> {
+        g1: G1::rand(&mut rng),
         g2_1: G2::rand(&mut rng),
         g2_x: G2::rand(&mut rng)
     };
 
-    let c = kzg.commit(&pk, &poly);
-    let c_ = kzg.commit(&pk, &poly_);
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let c_ = kzg.commit(&pk, &poly_).unwrap();
 
-    let v = kzg.evaluate(&poly, &z);
-    let v_ = kzg.evaluate(&poly_, &z);
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let v_ = kzg.evaluate(&poly_, &z).unwrap();
 
-    let p = kzg.open(&pk, &poly, &z, &v, &());
-    let p_ = kzg.open(&pk, &poly, &z_, &v, &());
+    let p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+    let p_ = kzg.open(&pk, &poly, &z_, &v, &()).unwrap();
 
-    let b = KZG10::verify(&c, &pk, &p, &z, &v, &());
-    assert_eq!(b, true);
+    let b = KZG10::verify(&c, &vk, &p, &z, &v, &()).unwrap();
+    assert!(b);
     
-    let b_ = KZG10::verify(&c_, &pk, &p, &z, &v, &());
-    assert_eq!(b_, false);
+    let b_ = KZG10::verify(&c_, &vk, &p, &z, &v, &()).unwrap();
+    assert!(!b_);
     
-    let b_ = KZG10::verify(&c, &pk_, &p, &z, &v, &());
-    assert_eq!(b_, false);
+    let b_ = KZG10::verify(&c, &vk_, &p, &z, &v, &()).unwrap();
+    assert!(!b_);
+
+    let b_ = KZG10::verify(&c, &vk, &p_, &z, &v, &()).unwrap();
+    assert!(!b_);
+
+    let b_ = KZG10::verify(&c, &vk, &p, &z_, &v, &()).unwrap();
+    assert!(!b_);
+
+    let b_ = KZG10::verify(&c, &vk, &p, &z, &v_, &()).unwrap();
+    assert!(!b_);
+}
+
+#[test]
+fn kzg10_commit_msm_test() {
+    let mut rng = test_rng();
+
+    let poly = poly_generator(4, 32, &mut rng);
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, _) = kzg.setup_with_rng(64, &mut rng);
+
+    // The multi-scalar multiplication agrees with summing each term separately.
+    let c = kzg.commit(&pk, &poly).unwrap();
+    for (p, c) in poly.iter().zip(c.iter()) {
+        let naive: G1 = p.coeffs.iter().zip(pk.g1_vec.iter()).map(|(a, g)| *g * a).sum();
+        assert_eq!(c.0, naive);
+    }
+}
+
+#[test]
+fn kzg10_find_invalid_test() {
+    let mut rng = test_rng();
+
+    let t = 4;
+    let d = 16;
+
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.setup_with_rng(d, &mut rng);
+
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let mut p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+
+    assert!(KZG10::verify_with_rng(&c, &vk, &p, &z, &v, &mut rng).unwrap());
+    assert_eq!(KZG10::find_invalid(&c, &vk, &p, &z, &v).unwrap(), None);
+
+    // A single bad opening fails the batched check, and is pinpointed by the
+    // per-pair one.
+    p[2][1] = G1::rand(&mut rng);
+    assert!(!KZG10::verify_with_rng(&c, &vk, &p, &z, &v, &mut rng).unwrap());
+    assert_eq!(KZG10::find_invalid(&c, &vk, &p, &z, &v).unwrap(), Some((2, 1)));
+
+    // Mismatched shapes are rejected rather than indexed out of bounds.
+    p[2].pop();
+    assert_eq!(KZG10::verify(&c, &vk, &p, &z, &v, &()), Err(PolyCommitError::LengthMismatch { expected: t, found: t - 1 }));
+}
+
+#[test]
+fn kzg10_hiding_test() {
+    let mut rng = test_rng();
+
+    let t = 4;
+    let d = 16;
+
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
 
-    let b_ = KZG10::verify(&c, &pk, &p_, &z, &v, &());
-    assert_eq!(b_, false);
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.setup_hiding_with_rng(d, &mut rng);
+
+    let (c, blinders) = kzg.commit_hiding(&pk, &poly, &mut rng).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open_hiding(&pk, &poly, &blinders, &z).unwrap();
+    assert!(KZG10::verify_hiding_with_rng(&c, &vk, &p, &z, &v, &mut rng).unwrap());
+
+    // The commitments are blinded: they differ from the plain ones, and from
+    // each other when the same polynomials are committed to again.
+    assert_ne!(c, kzg.commit(&pk.srs, &poly).unwrap());
+    let (c_, blinders_) = kzg.commit_hiding(&pk, &poly, &mut rng).unwrap();
+    assert_ne!(c, c_);
+    assert!(KZG10::verify_hiding(&c_, &vk, &kzg.open_hiding(&pk, &poly, &blinders_, &z).unwrap(), &z, &v).unwrap());
+
+    // Openings with the wrong values, blinding values or witnesses fail.
+    let mut v_ = v.clone();
+    v_[1][2] += Fr::ONE;
+    assert!(!KZG10::verify_hiding(&c, &vk, &p, &z, &v_).unwrap());
+
+    let mut p_ = p.clone();
+    p_[3][0].blinding_eval += Fr::ONE;
+    assert!(!KZG10::verify_hiding(&c, &vk, &p_, &z, &v).unwrap());
+
+    let mut p_ = p.clone();
+    p_[0][3].w = G1::rand(&mut rng);
+    assert!(!KZG10::verify_hiding(&c, &vk, &p_, &z, &v).unwrap());
+
+    assert!(!KZG10::verify_hiding(&c_, &vk, &p, &z, &v).unwrap());
+
+    // The blinding polynomials must be supplied for every polynomial.
+    assert_eq!(kzg.open_hiding(&pk, &poly, &blinders[1..], &z), Err(PolyCommitError::LengthMismatch { expected: t, found: t - 1 }));
+}
+
+#[test]
+fn kzg10_degree_bound_test() {
+    let mut rng = test_rng();
+
+    let max_deg = 32;
+    let bounds = [4, 10, 32, 10];
+
+    let poly: Vec<_> = bounds.iter().map(|&d| poly_generator(1, d, &mut rng).remove(0)).collect();
+    let z = point_generator(3, &mut rng);
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, _) = kzg.setup_with_rng(max_deg, &mut rng);
+    let vk = KZG10::bounded_verifier_key(&pk).unwrap();
+
+    let c = kzg.commit_bounded(&pk, &poly, &bounds).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open_bounded(&pk, &poly, &bounds, &z).unwrap();
+    assert!(KZG10::verify_bounded_with_rng(&c, &vk, &bounds, &p, &z, &v, &mut rng).unwrap());
+
+    // A bound looser than the one committed to does not verify either.
+    let mut bounds_ = bounds;
+    bounds_[1] = 12;
+    assert!(!KZG10::verify_bounded(&c, &vk, &bounds_, &p, &z, &v).unwrap());
 
-    let b_ = KZG10::verify(&c, &pk, &p, &z_, &v, &());
-    assert_eq!(b_, false);
+    // Committing to a polynomial beyond its bound fails.
+    let mut bounds_ = bounds;
+    bounds_[2] = 31;
+    assert_eq!(kzg.commit_bounded(&pk, &poly, &bounds_), Err(PolyCommitError::DegreeTooLarge { degree: 32, max_degree: 31 }));
 
-    let b_ = KZG10::verify(&c, &pk, &p, &z, &v_, &());
-    assert_eq!(b_, false);
+    // An honest commitment and openings of the polynomial of degree 32 under
+    // bound 32 do not verify against bound 31, as its shift is by x^0 and not
+    // by x.
+    assert!(!KZG10::verify_bounded(&c, &vk, &bounds_, &p, &z, &v).unwrap());
+
+    // Bounds beyond the maximum degree of the key are rejected.
+    let mut bounds_ = bounds;
+    bounds_[0] = 33;
+    assert_eq!(KZG10::verify_bounded(&c, &vk, &bounds_, &p, &z, &v), Err(PolyCommitError::DegreeTooLarge { degree: 33, max_degree: 32 }));
+}
+
+#[test]
+fn kzg10_evaluations_test() {
+    let mut rng = test_rng();
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (srs, vk) = kzg.setup_with_rng(63, &mut rng);
+    let pk = srs.lagrange_basis(64).unwrap();
+    assert_eq!(pk.lagrange_vec.len(), 64);
+
+    let poly = poly_generator(3, 63, &mut rng);
+    let evals: Vec<_> = poly.iter().map(|p| Evaluations::from_vec_and_domain(pk.domain.fft(&p.coeffs), pk.domain)).collect();
+
+    // The commitments and proofs are the same as for the interpolated
+    // polynomials, at points both outside and inside the domain.
+    let mut z = point_generator(3, &mut rng);
+    z.extend([pk.domain.element(0), pk.domain.element(17)]);
+    let c = kzg.commit_evaluations(&pk, &evals).unwrap();
+    assert_eq!(c, kzg.commit(&srs, &poly).unwrap());
+
+    let v = kzg.evaluate_evaluations(&evals, &z).unwrap();
+    assert_eq!(v, kzg.evaluate(&poly, &z).unwrap());
+
+    let p = kzg.open_evaluations(&pk, &evals, &z, &v).unwrap();
+    assert_eq!(p, kzg.open(&srs, &poly, &z, &v, &()).unwrap());
+    assert!(KZG10::verify_with_rng(&c, &vk, &p, &z, &v, &mut rng).unwrap());
+
+    // Polynomials of lower degree may be committed to over a smaller domain.
+    let pk = srs.lagrange_basis(20).unwrap();
+    assert_eq!(pk.domain.size(), 32);
+    let poly = poly_generator(1, 31, &mut rng);
+    let evals = [Evaluations::from_vec_and_domain(pk.domain.fft(&poly[0].coeffs), pk.domain)];
+    assert_eq!(kzg.commit_evaluations(&pk, &evals).unwrap(), kzg.commit(&srs, &poly).unwrap());
+}
+
+#[test]
+fn kzg10_open_all_on_domain_test() {
+    let mut rng = test_rng();
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.setup_with_rng(40, &mut rng);
+    let domain = Radix2EvaluationDomain::<Fr>::new(16).unwrap();
+
+    // Polynomials of degree below, at and above the size of the domain, as
+    // well as constants, over the domain and a coset of it.
+    let mut poly = poly_generator(1, 15, &mut rng);
+    poly.extend(poly_generator(1, 5, &mut rng));
+    poly.extend(poly_generator(1, 40, &mut rng));
+    poly.extend(poly_generator(1, 0, &mut rng));
+    for domain in [domain, domain.get_coset(Fr::GENERATOR).unwrap()] {
+        let z: Vec<Fr> = domain.elements().collect();
+        let v = kzg.evaluate(&poly, &z).unwrap();
+        let p = kzg.open_all_on_domain(&pk, &poly, domain).unwrap();
+        assert_eq!(p, kzg.open(&pk, &poly, &z, &v, &()).unwrap());
+
+        let c = kzg.commit(&pk, &poly).unwrap();
+        assert!(KZG10::verify_with_rng(&c, &vk, &p, &z, &v, &mut rng).unwrap());
+    }
 }
@@ -0,0 +1,131 @@
+// These tests pass with and without the `parallel` feature: the batched calls,
+// which are parallelised when it is enabled, must agree with computing each
+// polynomial and point on its own, and both builds must give the same known
+// answers.
+mod util;
+
+use util::{poly_generator, point_generator, test_rng};
+use ark_bls12_381::Bls12_381;
+use ark_serialize::CanonicalSerialize;
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::PolyCommit;
+
+#[test]
+fn kzg10_batched_matches_single_test() {
+    let mut rng = test_rng();
+
+    let t = 8;
+    let d = 32;
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.setup_with_rng(d, &mut rng);
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+    assert!(KZG10::verify(&c, &vk, &p, &z, &v, &()).unwrap());
+
+    for i in 0..t {
+        let single = &poly[i..i + 1];
+        assert_eq!(kzg.commit(&pk, single).unwrap()[0], c[i]);
+        for j in 0..t {
+            let point = &z[j..j + 1];
+            let v_ij = kzg.evaluate(single, point).unwrap();
+            assert_eq!(v_ij[0][0], v[i][j]);
+            assert_eq!(kzg.open(&pk, single, point, &v_ij, &()).unwrap()[0][0], p[i][j]);
+        }
+    }
+}
+
+#[test]
+fn gwc19_batched_matches_single_test() {
+    let mut rng = test_rng();
+
+    let t = 8;
+    let d = 32;
+
+    let mut gwc = GWC19::<Bls12_381>::new();
+    let (pk, vk) = gwc.setup_with_rng(d, &mut rng);
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+    let ver_params = point_generator(t, &mut rng);
+
+    let c = gwc.commit(&pk, &poly).unwrap();
+    let v = gwc.evaluate(&poly, &z).unwrap();
+    let p = gwc.open(&pk, &poly, &z, &v, &ver_params).unwrap();
+    assert!(GWC19::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap());
+
+    for i in 0..t {
+        assert_eq!(gwc.commit(&pk, &poly[i..i + 1]).unwrap()[0], c[i]);
+
+        // The witness for point `i` only depends on that point's column of `v`.
+        let v_i: Vec<Vec<_>> = v.iter().map(|v_j| vec![v_j[i]]).collect();
+        let p_i = gwc.open(&pk, &poly, &z[i..i + 1], &v_i, &ver_params[i..i + 1].to_vec()).unwrap();
+        assert_eq!(p_i[0], p[i]);
+    }
+}
+
+#[test]
+fn djba21_batched_matches_single_test() {
+    let mut rng = test_rng();
+
+    let t = 8;
+    let d = 32;
+
+    let mut djba = DJBA21::<Bls12_381>::new();
+    let (pk, _) = djba.setup_with_rng(d, &mut rng);
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+
+    let c = djba.commit(&pk, &poly).unwrap();
+    let v = djba.evaluate(&poly, &z).unwrap();
+
+    for i in 0..t {
+        let single = &poly[i..i + 1];
+        assert_eq!(djba.commit(&pk, single).unwrap()[0], c[i]);
+        assert_eq!(djba.evaluate(single, &z).unwrap()[0], v[i]);
+    }
+}
+
+/// Returns the compressed encoding of `value` in hex.
+fn compressed_hex<T: CanonicalSerialize>(value: &T) -> String {
+    let mut bytes = vec![];
+    value.serialize_compressed(&mut bytes).unwrap();
+    hex::encode(bytes)
+}
+
+// The outputs for a fixed seed, which must be the same with and without the
+// `parallel` feature.
+#[test]
+fn known_answer_test() {
+    let mut rng = test_rng();
+
+    let t = 2;
+    let d = 8;
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, _) = kzg.setup_with_rng(d, &mut rng);
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+    let ver_params = point_generator(t, &mut rng);
+
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+    assert_eq!(compressed_hex(&c[1]), "a3aaf456ac22e39bb594ebeee501603b0bca508becd70163b768be307e4f2dfbb86b8567d546967a9c884c0660c4836f");
+    assert_eq!(compressed_hex(&p[1][0]), "ae697f5cb1e0c57ed90c0378727eb61ae9fdf572e2f17beeec20bbbc6a0d9e95810ab266cf7fac527a0a9a4ebd29ce84");
+
+    let gwc = GWC19::<Bls12_381>::new();
+    let p = gwc.open(&pk, &poly, &z, &v, &ver_params).unwrap();
+    assert_eq!(compressed_hex(&p[1]), "929d71097374df714014903e988d70e0ceac811ec0b86ec41c828f42fcdbd46cb8e201e67fa3ff43dad3ef356e25a9ab");
+
+    let djba = DJBA21::<Bls12_381>::new();
+    let v = djba.evaluate(&poly, &z).unwrap();
+    let p = djba.open(&pk, &poly, &z, &v, &(ver_params[0], ver_params[1])).unwrap();
+    assert_eq!(compressed_hex(&p.0), "864b3c7e2071db38dd94c6e26806b93b4d35cb06edde8bc37d2674a3da6d1b9a986375b3a5cd1903db69b41b8c57ac05");
+    assert_eq!(compressed_hex(&p.1), "8b3577ff57b9e13f819981048d70a3426efba305b5e4fd5f888e9072cb053650b75bf0e53f253110eca7d7203cade01c");
+}
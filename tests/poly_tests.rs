@@ -0,0 +1,45 @@
+mod util;
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::{Field, Zero};
+use ark_poly::Polynomial;
+use kzg_commitments::utils::poly::{lagrange_evaluate, lagrange_interpolate, vanishing_poly};
+use util::{point_generator, poly_generator, test_rng};
+
+#[test]
+fn vanishing_poly_test() {
+    let mut rng = test_rng();
+
+    assert_eq!(vanishing_poly::<Bls12_381>(&[]).coeffs, vec![Fr::ONE]);
+
+    for n in [1, 2, 3, 5, 8, 11, 100] {
+        let z = point_generator(n, &mut rng);
+        let zt = vanishing_poly::<Bls12_381>(&z);
+
+        // Z_T is monic of degree |T|, and vanishes exactly on T.
+        assert_eq!(zt.degree(), n);
+        assert_eq!(zt.coeffs[n], Fr::ONE);
+        assert!(z.iter().all(|t| zt.evaluate(t).is_zero()));
+
+        let x = point_generator(1, &mut rng)[0];
+        let expected: Fr = z.iter().map(|t| x - t).product();
+        assert_eq!(zt.evaluate(&x), expected);
+    }
+}
+
+#[test]
+fn lagrange_interpolate_test() {
+    let mut rng = test_rng();
+
+    for n in [1, 3, 6, 9, 33] {
+        let poly = poly_generator(1, n - 1, &mut rng).pop().unwrap();
+        let points: Vec<(Fr, Fr)> = point_generator(n, &mut rng).into_iter().map(|x| (x, poly.evaluate(&x))).collect();
+
+        assert_eq!(lagrange_interpolate::<Bls12_381>(&points).unwrap(), poly);
+
+        // Evaluating directly agrees with interpolating, both on and off the points.
+        let x = point_generator(1, &mut rng)[0];
+        assert_eq!(lagrange_evaluate::<Bls12_381>(&points, x).unwrap(), poly.evaluate(&x));
+        assert_eq!(lagrange_evaluate::<Bls12_381>(&points, points[0].0).unwrap(), points[0].1);
+    }
+}
@@ -0,0 +1,105 @@
+mod util;
+
+use util::{point_generator, test_rng};
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ec::AdditiveGroup;
+use ark_ff::{Field, UniformRand};
+use ark_poly::{DenseMultilinearExtension, MultilinearExtension};
+use kzg_commitments::poly_commit::PolyCommitError;
+use kzg_commitments::pst13::PST13;
+
+fn mle_generator(count: usize, num_vars: usize, rng: &mut impl ark_std::rand::Rng) -> Vec<DenseMultilinearExtension<Fr>> {
+    (0..count).map(|_| DenseMultilinearExtension::rand(num_vars, rng)).collect()
+}
+
+#[test]
+fn pst13_test() {
+    let mut rng = test_rng();
+
+    for num_vars in 0..=6 {
+        for count in [1, 3] {
+            let poly = mle_generator(count, num_vars, &mut rng);
+            let point = point_generator(num_vars, &mut rng);
+
+            let pst = PST13::<Bls12_381>::new();
+            let (pk, vk) = pst.setup_with_rng(num_vars, &mut rng);
+            assert_eq!(pk.num_vars(), num_vars);
+
+            let c = pst.commit(&pk, &poly).unwrap();
+            let v = pst.evaluate(&poly, &point).unwrap();
+            let p = pst.open(&pk, &poly, &point).unwrap();
+            assert!(p.iter().all(|p_i| p_i.len() == num_vars));
+            assert!(PST13::verify_with_rng(&c, &vk, &p, &point, &v, &mut rng).unwrap());
+            assert!(PST13::verify(&c, &vk, &p, &point, &v).unwrap());
+
+            // A wrong value does not verify.
+            let mut v_ = v.clone();
+            v_[count - 1] += Fr::ONE;
+            assert!(!PST13::verify(&c, &vk, &p, &point, &v_).unwrap());
+
+            if num_vars > 0 {
+                // Nor do the same proofs at another point, or with a witness changed.
+                let mut point_ = point.clone();
+                point_[num_vars - 1] = Fr::rand(&mut rng);
+                assert!(!PST13::verify(&c, &vk, &p, &point_, &v).unwrap());
+
+                let mut p_ = p.clone();
+                p_[0][0].double_in_place();
+                assert!(!PST13::verify(&c, &vk, &p_, &point, &v).unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn pst13_hypercube_test() {
+    let mut rng = test_rng();
+
+    let num_vars = 4;
+    let poly = mle_generator(2, num_vars, &mut rng);
+    let pst = PST13::<Bls12_381>::new();
+    let (pk, vk) = pst.setup_with_rng(num_vars, &mut rng);
+    let c = pst.commit(&pk, &poly).unwrap();
+
+    // At a point of the hypercube the values are the evaluations themselves,
+    // with the first variable the lowest bit of the index.
+    let b = 0b1011;
+    let point: Vec<Fr> = (0..num_vars).map(|k| if b >> k & 1 == 1 { Fr::ONE } else { Fr::ZERO }).collect();
+    let v = pst.evaluate(&poly, &point).unwrap();
+    assert_eq!(v, vec![poly[0].evaluations[b], poly[1].evaluations[b]]);
+    let p = pst.open(&pk, &poly, &point).unwrap();
+    assert!(PST13::verify_with_rng(&c, &vk, &p, &point, &v, &mut rng).unwrap());
+
+    // Commitments are additive.
+    let sum = &poly[0] + &poly[1];
+    assert_eq!(pst.commit(&pk, &[sum]).unwrap()[0], c[0] + c[1]);
+}
+
+#[test]
+fn pst13_errors_test() {
+    let mut rng = test_rng();
+
+    let pst = PST13::<Bls12_381>::new();
+    let (pk, vk) = pst.setup_with_rng(3, &mut rng);
+    let poly = mle_generator(2, 3, &mut rng);
+    let point = point_generator(3, &mut rng);
+    let c = pst.commit(&pk, &poly).unwrap();
+    let v = pst.evaluate(&poly, &point).unwrap();
+    let p = pst.open(&pk, &poly, &point).unwrap();
+
+    // Polynomials and points in the wrong number of variables.
+    let other = mle_generator(1, 4, &mut rng);
+    assert_eq!(pst.commit(&pk, &other), Err(PolyCommitError::LengthMismatch { expected: 3, found: 4 }));
+    assert_eq!(pst.open(&pk, &poly, &point[1..]), Err(PolyCommitError::LengthMismatch { expected: 3, found: 2 }));
+    assert_eq!(PST13::verify(&c, &vk, &p, &point[1..], &v), Err(PolyCommitError::LengthMismatch { expected: 3, found: 2 }));
+    assert_eq!(PST13::verify(&c, &vk, &p, &point, &v[1..]), Err(PolyCommitError::LengthMismatch { expected: 2, found: 1 }));
+    assert_eq!(pst.commit(&pk, &[]), Err(PolyCommitError::EmptyInput));
+
+    // Malformed keys.
+    let mut pk_ = pk.clone();
+    pk_.g1_vecs[1].pop();
+    assert_eq!(pst.commit(&pk_, &poly), Err(PolyCommitError::MalformedKey));
+    let mut vk_ = vk.clone();
+    vk_.g2 = Default::default();
+    assert_eq!(PST13::verify(&c, &vk_, &p, &point, &v), Err(PolyCommitError::MalformedKey));
+}
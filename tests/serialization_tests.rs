@@ -0,0 +1,93 @@
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_ff::UniformRand;
+use ark_poly::{DenseUVPolynomial, univariate::DensePolynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::rand::{SeedableRng, rngs::StdRng};
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::shplonk::SHPLONK;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::NonInteractive;
+use kzg_commitments::srs::Srs;
+
+fn round_trip<T: CanonicalSerialize + CanonicalDeserialize>(value: &T) -> T {
+    let mut compressed = vec![];
+    value.serialize_compressed(&mut compressed).unwrap();
+    let mut uncompressed = vec![];
+    value.serialize_uncompressed(&mut uncompressed).unwrap();
+    assert_eq!(compressed.len(), value.compressed_size());
+    assert_eq!(uncompressed.len(), value.uncompressed_size());
+
+    // Both encodings decode to values which encode identically.
+    let from_uncompressed = T::deserialize_uncompressed(uncompressed.as_slice()).unwrap();
+    let mut bytes = vec![];
+    from_uncompressed.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes, compressed);
+
+    T::deserialize_with_mode(compressed.as_slice(), Compress::Yes, Validate::Yes).unwrap()
+}
+
+fn serialization_helper<E: Pairing, P: NonInteractive<E>>() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let d = 16;
+    let poly: Vec<_> = (0..4).map(|_| DensePolynomial::<E::ScalarField>::rand(d, &mut rng)).collect();
+    let z: Vec<_> = (0..3).map(|_| E::ScalarField::rand(&mut rng)).collect();
+
+    let mut pc = P::new();
+    let (pk, vk) = pc.setup_with_rng(d, &mut rng);
+    let c = pc.commit(&pk, &poly).unwrap();
+    let v = pc.evaluate(&poly, &z).unwrap();
+    let p = pc.open_non_interactive(&pk, &c, &poly, &z, &v).unwrap();
+
+    // Everything read back from bytes can be used in place of the original.
+    let pk = round_trip(&pk);
+    let vk = round_trip(&vk);
+    let c = round_trip(&c);
+    let v: Vec<P::Evaluation> = round_trip(&v);
+    let p = round_trip(&p);
+    assert!(P::verify_non_interactive(&c, &vk, &p, &z, &v).unwrap());
+    assert!(P::verify_non_interactive(&pc.commit(&pk, &poly).unwrap(), &vk, &pc.open_non_interactive(&pk, &c, &poly, &z, &v).unwrap(), &z, &v).unwrap());
+}
+
+#[test]
+fn serialization_test() {
+    serialization_helper::<Bls12_381, KZG10<Bls12_381>>();
+    serialization_helper::<Bls12_381, GWC19<Bls12_381>>();
+    serialization_helper::<Bls12_381, DJBA21<Bls12_381>>();
+    serialization_helper::<Bls12_381, SHPLONK<Bls12_381>>();
+
+    serialization_helper::<Bls12_377, KZG10<Bls12_377>>();
+    serialization_helper::<Bls12_377, GWC19<Bls12_377>>();
+    serialization_helper::<Bls12_377, DJBA21<Bls12_377>>();
+    serialization_helper::<Bls12_377, SHPLONK<Bls12_377>>();
+
+    serialization_helper::<Bn254, KZG10<Bn254>>();
+    serialization_helper::<Bn254, GWC19<Bn254>>();
+    serialization_helper::<Bn254, DJBA21<Bn254>>();
+    serialization_helper::<Bn254, SHPLONK<Bn254>>();
+}
+
+#[test]
+fn deserialization_invalid_test() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let srs = Srs::<Bls12_381>::generate(4, &mut rng);
+    let mut bytes = vec![];
+    srs.serialize_compressed(&mut bytes).unwrap();
+    assert!(Srs::<Bls12_381>::deserialize_compressed(bytes.as_slice()).is_ok());
+
+    // Truncated input.
+    assert!(Srs::<Bls12_381>::deserialize_compressed(&bytes[..bytes.len() - 1]).is_err());
+
+    // The first G1 point follows the length prefix: flipping bits of its
+    // x-coordinate leaves (with overwhelming probability) no point on the curve,
+    // or one outside the subgroup, both of which validation rejects.
+    let mut corrupted = bytes.clone();
+    corrupted[8 + 47] ^= 0x01;
+    corrupted[8 + 46] ^= 0x80;
+    assert!(Srs::<Bls12_381>::deserialize_compressed(corrupted.as_slice()).is_err());
+}
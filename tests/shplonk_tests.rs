@@ -0,0 +1,211 @@
+mod util;
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::UniformRand;
+use kzg_commitments::shplonk::SHPLONK;
+use kzg_commitments::poly_commit::PolyCommit;
+use kzg_commitments::srs::VerifierKey;
+use util::{point_generator, poly_generator, test_rng};
+
+fn shplonk_helper(poly_count: usize, poly_deg: usize, point_count: usize) -> bool {
+    let mut rng = test_rng();
+    
+    let poly = poly_generator(poly_count, poly_deg, &mut rng);
+
+    let z = point_generator(point_count, &mut rng);
+
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+    let mut shplonk = SHPLONK::<Bls12_381>::new();
+
+    let (pk, vk) = shplonk.setup_with_rng(poly_deg, &mut rng);
+
+    let c = shplonk.commit(&pk, &poly).unwrap();
+
+    let v = shplonk.evaluate(&poly, &z).unwrap();
+
+    let p = shplonk.open(&pk, &poly, &z, &v, &ver_params).unwrap();
+
+    SHPLONK::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap()
+}
+
+#[test]
+fn shplonk_test() -> Result<(), ()> {
+    let poly_deg_vals = [8, 16];
+    let poly_count_vals = [1, 2, 4, 8, 16, 32];
+
+    for &poly_count in &poly_count_vals {
+        for &poly_deg in &poly_deg_vals {
+            if !shplonk_helper(poly_count, poly_deg, poly_count) {
+                println!(
+                    "params: polys: {}, deg: {}, points: {}",
+                    poly_count, poly_deg, poly_count
+                );
+                return Err(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn shplonk_poly_point_count_test() {
+    // The number of polynomials and the number of points vary independently.
+    for &(poly_count, point_count) in &[(20, 2), (1, 64), (5, 1), (1, 3), (7, 4), (3, 10)] {
+        assert!(shplonk_helper(poly_count, 16, point_count), "params: polys: {poly_count}, deg: 16, points: {point_count}");
+    }
+}
+
+#[test]
+fn shplonk_odd_point_count_test() {
+    for &(count, deg) in &[(1, 8), (3, 8), (5, 16), (7, 10), (13, 20), (100, 128)] {
+        assert!(shplonk_helper(count, deg, count), "params: polys: {count}, deg: {deg}, points: {count}");
+    }
+}
+
+#[test]
+pub fn shplonk_basic_test() {
+    let mut rng = test_rng();
+
+    let t = 8;
+    let d = 16;
+    let max_deg = 128;
+    
+    let poly = poly_generator(t, d, &mut rng);
+    let poly_ = poly_generator(t, d, &mut rng);
+    
+    let z = point_generator(t, &mut rng);
+    let z_ = point_generator(t, &mut rng);
+
+    let mut shplonk = SHPLONK::<Bls12_381>::new();
+
+    type G1 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G1;
+    type G2 = <ark_ec::models::bls12::Bls12<ark_bls12_381::Config> as ark_ec::pairing::Pairing>::G2;
+    
+    let (pk, vk) = shplonk.setup_with_rng(max_deg, &mut rng);
+    let vk_ = VerifierKey::<Bls12_381> {
+        g1: G1::rand(&mut rng),
+        g2_1: G2::rand(&mut rng),
+        g2_x: G2::rand(&mut rng)
+    };
+
+    let c = shplonk.commit(&pk, &poly).unwrap();
+    let c_ = shplonk.commit(&pk, &poly_).unwrap();
+
+    let v = shplonk.evaluate(&poly, &z).unwrap();
+    let v_ = shplonk.evaluate(&poly_, &z).unwrap();
+
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    let ver_params_ = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+    let p = shplonk.open(&pk, &poly, &z, &v, &ver_params).unwrap();
+    let p_ = shplonk.open(&pk, &poly, &z_, &v, &ver_params).unwrap();
+
+    let b = SHPLONK::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap();
+    assert!(b);
+    
+    let b_ = SHPLONK::verify(&c_, &vk, &p, &z, &v, &ver_params).unwrap();
+    assert!(!b_);
+    
+    let b_ = SHPLONK::verify(&c, &vk_, &p, &z, &v, &ver_params).unwrap();
+    assert!(!b_);
+
+    let b_ = SHPLONK::verify(&c, &vk, &p_, &z, &v, &ver_params).unwrap();
+    assert!(!b_);
+
+    let b_ = SHPLONK::verify(&c, &vk, &p, &z_, &v, &ver_params).unwrap();
+    assert!(!b_);
+
+    let b_ = SHPLONK::verify(&c, &vk, &p, &z, &v_, &ver_params).unwrap();
+    assert!(!b_);
+
+    let b_ = SHPLONK::verify(&c, &vk, &p, &z, &v, &ver_params_).unwrap();
+    assert!(!b_);
+}
+
+#[test]
+fn shplonk_subsets_test() {
+    let mut rng = test_rng();
+
+    let d = 16;
+
+    let mut shplonk = SHPLONK::<Bls12_381>::new();
+    let (pk, vk) = shplonk.setup_with_rng(d, &mut rng);
+
+    let poly = poly_generator(4, d, &mut rng);
+    let t = point_generator(6, &mut rng);
+
+    // Each polynomial is opened on its own subset of the points, some of
+    // which overlap, and one of which is the whole of T.
+    let sets = vec![
+        vec![t[0], t[1]],
+        vec![t[2]],
+        t.clone(),
+        vec![t[5], t[3], t[1]],
+    ];
+    let queries: Vec<_> = poly.iter().cloned().zip(sets.iter().cloned()).collect();
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+    let c = shplonk.commit(&pk, &poly).unwrap();
+    let v = shplonk.evaluate_subsets(&queries).unwrap();
+    let p = shplonk.open_subsets(&pk, &queries, &v, &ver_params).unwrap();
+    assert!(SHPLONK::verify_subsets(&c, &vk, &p, &sets, &v, &ver_params).unwrap());
+
+    // Claiming a polynomial was opened on a different set fails.
+    let mut sets_ = sets.clone();
+    sets_[1] = vec![t[4]];
+    assert!(!SHPLONK::verify_subsets(&c, &vk, &p, &sets_, &v, &ver_params).unwrap());
+
+    // As does a wrong evaluation.
+    let mut v_ = v.clone();
+    v_[3][1] = Fr::rand(&mut rng);
+    assert!(!SHPLONK::verify_subsets(&c, &vk, &p, &sets, &v_, &ver_params).unwrap());
+
+    // Opening every polynomial on the same set agrees with `open`.
+    let queries: Vec<_> = poly.iter().map(|p| (p.clone(), t.clone())).collect();
+    let v = shplonk.evaluate_subsets(&queries).unwrap();
+    assert_eq!(v, shplonk.evaluate(&poly, &t).unwrap());
+    assert_eq!(shplonk.open_subsets(&pk, &queries, &v, &ver_params).unwrap(), shplonk.open(&pk, &poly, &t, &v, &ver_params).unwrap());
+}
+
+#[test]
+fn shplonk_grouped_sets_test() {
+    let mut rng = test_rng();
+
+    let d = 16;
+
+    let mut shplonk = SHPLONK::<Bls12_381>::new();
+    let (pk, vk) = shplonk.setup_with_rng(d, &mut rng);
+
+    let poly = poly_generator(6, d, &mut rng);
+    let t = point_generator(5, &mut rng);
+
+    // Several polynomials share each set of points, and the sharing
+    // polynomials are not adjacent.
+    let sets = vec![
+        vec![t[0], t[1]],
+        vec![t[2], t[3], t[4]],
+        vec![t[0], t[1]],
+        vec![t[4]],
+        vec![t[2], t[3], t[4]],
+        vec![t[0], t[1]],
+    ];
+    let queries: Vec<_> = poly.iter().cloned().zip(sets.iter().cloned()).collect();
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+    let c = shplonk.commit(&pk, &poly).unwrap();
+    let v = shplonk.evaluate_subsets(&queries).unwrap();
+    let p = shplonk.open_subsets(&pk, &queries, &v, &ver_params).unwrap();
+    assert!(SHPLONK::verify_subsets(&c, &vk, &p, &sets, &v, &ver_params).unwrap());
+
+    // Swapping the commitments of two polynomials in the same group fails.
+    let mut c_ = c.clone();
+    c_.swap(0, 2);
+    assert!(!SHPLONK::verify_subsets(&c_, &vk, &p, &sets, &v, &ver_params).unwrap());
+
+    // As does swapping their values.
+    let mut v_ = v.clone();
+    v_.swap(1, 4);
+    assert!(!SHPLONK::verify_subsets(&c, &vk, &p, &sets, &v_, &ver_params).unwrap());
+}
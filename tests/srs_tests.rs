@@ -0,0 +1,123 @@
+mod util;
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::UniformRand;
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::{PolyCommit, PolyCommitError};
+use kzg_commitments::srs::{Srs, verify_powers_with_rng};
+use util::{point_generator, poly_generator, test_rng};
+
+type G1Affine = <Bls12_381 as ark_ec::pairing::Pairing>::G1Affine;
+type G2 = <Bls12_381 as ark_ec::pairing::Pairing>::G2;
+
+#[test]
+fn srs_well_formed_test() {
+    let mut rng = test_rng();
+
+    let (pk, _) = KZG10::<Bls12_381>::new().setup_with_rng(64, &mut rng);
+    assert!(pk.is_well_formed());
+    assert!(verify_powers_with_rng::<Bls12_381, _>(&pk.g1_vec, &pk.g2_1, &pk.g2_x, &mut rng));
+
+    let (pk, _) = GWC19::<Bls12_381>::new().setup_with_rng(64, &mut rng);
+    assert!(pk.is_well_formed());
+
+    let (pk, _) = DJBA21::<Bls12_381>::new().setup_with_rng(64, &mut rng);
+    assert!(pk.is_well_formed());
+}
+
+#[test]
+fn srs_malformed_test() {
+    let mut rng = test_rng();
+
+    let (pk, _) = KZG10::<Bls12_381>::new().setup_with_rng(64, &mut rng);
+
+    let mut pk_ = pk.clone();
+    pk_.g1_vec[37] = G1Affine::rand(&mut rng);
+    assert!(!pk_.is_well_formed());
+
+    let mut pk_ = pk.clone();
+    pk_.g1_vec.swap(10, 11);
+    assert!(!pk_.is_well_formed());
+
+    let mut pk_ = pk.clone();
+    pk_.g2_x = G2::rand(&mut rng);
+    assert!(!pk_.is_well_formed());
+
+    let mut pk_ = pk.clone();
+    pk_.g1_vec = vec![G1Affine::rand(&mut rng); 65];
+    assert!(!pk_.is_well_formed());
+
+    let mut pk_ = pk.clone();
+    pk_.g1_vec.clear();
+    assert!(!pk_.is_well_formed());
+}
+
+#[test]
+fn shared_srs_test() {
+    let mut rng = test_rng();
+
+    let t = 4;
+    let d = 16;
+
+    // One setup, used by every scheme without converting between key types.
+    let srs = Srs::<Bls12_381>::generate(d, &mut rng);
+    let vk = srs.verifier_key();
+    assert_eq!(srs.max_degree(), d);
+
+    let poly = poly_generator(t, d, &mut rng);
+    let z = point_generator(t, &mut rng);
+
+    let kzg = KZG10::<Bls12_381>::new();
+    let c_kzg = kzg.commit(&srs, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(&srs, &poly, &z, &v, &()).unwrap();
+    assert!(KZG10::verify(&c_kzg, &vk, &p, &z, &v, &()).unwrap());
+
+    let ver_params = point_generator(t, &mut rng);
+    let gwc = GWC19::<Bls12_381>::new();
+    let c_gwc = gwc.commit(&srs, &poly).unwrap();
+    let v = gwc.evaluate(&poly, &z).unwrap();
+    let p = gwc.open(&srs, &poly, &z, &v, &ver_params).unwrap();
+    assert!(GWC19::verify(&c_gwc, &vk, &p, &z, &v, &ver_params).unwrap());
+
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    let djba = DJBA21::<Bls12_381>::new();
+    let c_djba = djba.commit(&srs, &poly).unwrap();
+    let v = djba.evaluate(&poly, &z).unwrap();
+    let p = djba.open(&srs, &poly, &z, &v, &ver_params).unwrap();
+    assert!(DJBA21::verify(&c_djba, &vk, &p, &z, &v, &ver_params).unwrap());
+
+    // All three schemes commit in the same way, so the commitments agree.
+    assert_eq!(c_kzg, c_gwc);
+    assert_eq!(c_kzg, c_djba);
+}
+
+#[test]
+fn trim_test() {
+    let mut rng = test_rng();
+
+    let srs = Srs::<Bls12_381>::generate(64, &mut rng);
+    let poly = poly_generator(4, 16, &mut rng);
+    let z = point_generator(4, &mut rng);
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.trim(&srs, 16).unwrap();
+    assert_eq!(pk.max_degree(), 16);
+    assert_eq!(vk, srs.verifier_key());
+
+    // The trimmed key commits exactly as the full SRS does.
+    let c = kzg.commit(&pk, &poly).unwrap();
+    assert_eq!(c, kzg.commit(&srs, &poly).unwrap());
+
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+    assert!(KZG10::verify(&c, &vk, &p, &z, &v, &()).unwrap());
+}
+
+#[test]
+fn trim_too_large_test() {
+    let srs = Srs::<Bls12_381>::generate(8, &mut test_rng());
+    assert_eq!(srs.trim(16).err(), Some(PolyCommitError::DegreeTooLarge { degree: 16, max_degree: 8 }));
+}
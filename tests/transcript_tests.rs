@@ -0,0 +1,99 @@
+mod util;
+
+use util::{poly_generator, point_generator, test_rng};
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::UniformRand;
+use kzg_commitments::commitment::Commitment;
+use kzg_commitments::djba21::DJBA21;
+use kzg_commitments::gwc19::GWC19;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::{NonInteractive, PolyCommit};
+use kzg_commitments::shplonk::SHPLONK;
+use kzg_commitments::transcript::Transcript;
+
+type G1 = <Bls12_381 as ark_ec::pairing::Pairing>::G1;
+
+#[test]
+fn transcript_test() {
+    let mut rng = test_rng();
+    let z = point_generator(4, &mut rng);
+
+    let mut a = Transcript::new(b"test");
+    a.append(b"points", z.as_slice());
+    let mut b = a.clone();
+
+    // The same messages give the same challenges, but successive challenges differ.
+    let x: Fr = a.challenge(b"x");
+    assert_eq!(x, b.challenge(b"x"));
+    assert_ne!(x, a.challenge::<Fr>(b"x"));
+
+    // Changing the domain, a label or a message changes the challenge.
+    let mut c = Transcript::new(b"other");
+    c.append(b"points", z.as_slice());
+    assert_ne!(x, c.challenge(b"x"));
+
+    let mut c = Transcript::new(b"test");
+    c.append(b"point", z.as_slice());
+    assert_ne!(x, c.challenge(b"x"));
+
+    let mut c = Transcript::new(b"test");
+    c.append(b"points", &z[1..]);
+    assert_ne!(x, c.challenge(b"x"));
+}
+
+fn non_interactive_helper<P: NonInteractive<Bls12_381, Commitment = Vec<Commitment<Bls12_381>>>>(poly_count: usize, point_count: usize) {
+    let mut rng = test_rng();
+
+    let d = 16;
+    let poly = poly_generator(poly_count, d, &mut rng);
+    let z = point_generator(point_count, &mut rng);
+    let z_ = point_generator(point_count, &mut rng);
+
+    let mut pc = P::new();
+    let (pk, vk) = pc.setup_with_rng(d, &mut rng);
+    let c = pc.commit(&pk, &poly).unwrap();
+    let v = pc.evaluate(&poly, &z).unwrap();
+    let p = pc.open_non_interactive(&pk, &c, &poly, &z, &v).unwrap();
+    assert!(P::verify_non_interactive(&c, &vk, &p, &z, &v).unwrap());
+
+    // The proof is bound to the commitments, points and evaluations.
+    let mut c_ = c.clone();
+    c_[0] = Commitment(G1::rand(&mut rng));
+    assert!(!P::verify_non_interactive(&c_, &vk, &p, &z, &v).unwrap());
+    assert!(!P::verify_non_interactive(&c, &vk, &p, &z_, &v).unwrap());
+    let v_ = pc.evaluate(&poly_generator(poly_count, d, &mut rng), &z).unwrap();
+    assert!(!P::verify_non_interactive(&c, &vk, &p, &z, &v_).unwrap());
+}
+
+#[test]
+fn non_interactive_test() {
+    for &(poly_count, point_count) in &[(1, 1), (4, 4), (5, 2), (1, 7)] {
+        non_interactive_helper::<KZG10<Bls12_381>>(poly_count, point_count);
+        non_interactive_helper::<GWC19<Bls12_381>>(poly_count, point_count);
+        non_interactive_helper::<DJBA21<Bls12_381>>(poly_count, point_count);
+        non_interactive_helper::<SHPLONK<Bls12_381>>(poly_count, point_count);
+    }
+}
+
+#[test]
+fn djba21_non_interactive_test() {
+    let mut rng = test_rng();
+
+    let d = 16;
+    let poly = poly_generator(4, d, &mut rng);
+    let z = point_generator(4, &mut rng);
+
+    let mut djba = DJBA21::<Bls12_381>::new();
+    let (pk, vk) = djba.setup_with_rng(d, &mut rng);
+    let c = djba.commit(&pk, &poly).unwrap();
+    let v = djba.evaluate(&poly, &z).unwrap();
+    let p = djba.open_non_interactive(&pk, &c, &poly, &z, &v).unwrap();
+
+    // `z` is derived from `W`, so changing either half of the proof fails.
+    assert!(!DJBA21::verify_non_interactive(&c, &vk, &(G1::rand(&mut rng), p.1), &z, &v).unwrap());
+    assert!(!DJBA21::verify_non_interactive(&c, &vk, &(p.0, G1::rand(&mut rng)), &z, &v).unwrap());
+
+    // The interactive verifier with parameters other than the derived ones fails.
+    let ver_params = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    assert!(!DJBA21::verify(&c, &vk, &p, &z, &v, &ver_params).unwrap());
+}
@@ -0,0 +1,95 @@
+mod util;
+
+use ark_bls12_381::{Bls12_381, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use kzg_commitments::kzg10::KZG10;
+use kzg_commitments::poly_commit::PolyCommit;
+use kzg_commitments::trusted_setup::{TrustedSetup, TrustedSetupError};
+use kzg_commitments::utils::poly::bit_reverse_permutation;
+use util::{point_generator, poly_generator, test_rng};
+
+// The first 8 G1 points and 2 G2 points of the Ethereum KZG ceremony output,
+// with the Lagrange points recomputed for a domain of size 8.
+const SETUP_TXT: &str = include_str!("fixtures/trusted_setup_8.txt");
+const SETUP_JSON: &str = include_str!("fixtures/trusted_setup_8.json");
+
+#[test]
+fn trusted_setup_load_test() {
+    let txt = TrustedSetup::from_txt(SETUP_TXT).unwrap();
+    let json = TrustedSetup::from_json(SETUP_JSON).unwrap();
+
+    assert_eq!(txt.g1_monomial, json.g1_monomial);
+    assert_eq!(txt.g1_lagrange, json.g1_lagrange);
+    assert_eq!(txt.g2_monomial, json.g2_monomial);
+
+    assert_eq!(txt.g1_monomial.len(), 8);
+    assert_eq!(txt.g2_monomial.len(), 2);
+    assert_eq!(txt.g1_monomial[0], G1Affine::generator());
+    assert_eq!(txt.g2_monomial[0], G2Affine::generator());
+
+    let file = TrustedSetup::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/trusted_setup_8.json")).unwrap();
+    assert_eq!(file.g1_monomial, txt.g1_monomial);
+
+    // The Lagrange points are the inverse FFT of the monomial points, in
+    // bit-reversed order.
+    let mut lagrange = txt.srs().lagrange_basis(8).unwrap().lagrange_vec;
+    bit_reverse_permutation(&mut lagrange);
+    assert_eq!(lagrange, txt.g1_lagrange);
+}
+
+#[test]
+fn trusted_setup_mainnet_lagrange_test() {
+    // The mainnet setup lists the Lagrange points in natural order, and they
+    // are loaded in bit-reversed order.
+    let setup = TrustedSetup::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/trusted_setup_4096.txt")).unwrap();
+    let mut lagrange = setup.srs().lagrange_basis(4096).unwrap().lagrange_vec;
+    bit_reverse_permutation(&mut lagrange);
+    assert_eq!(lagrange, setup.g1_lagrange);
+}
+
+#[test]
+fn trusted_setup_kzg10_test() {
+    let mut rng = test_rng();
+
+    let setup = TrustedSetup::from_txt(SETUP_TXT).unwrap();
+    let srs = setup.srs();
+    assert!(srs.is_well_formed());
+
+    let mut kzg = KZG10::<Bls12_381>::new();
+    let (pk, vk) = kzg.trim(&srs, 7).unwrap();
+
+    let poly = poly_generator(4, 7, &mut rng);
+    let z = point_generator(4, &mut rng);
+
+    let c = kzg.commit(&pk, &poly).unwrap();
+    let v = kzg.evaluate(&poly, &z).unwrap();
+    let p = kzg.open(&pk, &poly, &z, &v, &()).unwrap();
+    assert!(KZG10::verify(&c, &vk, &p, &z, &v, &()).unwrap());
+}
+
+#[test]
+fn trusted_setup_invalid_test() {
+    let generator = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    // On the curve, but not in the prime-order subgroup.
+    let not_in_subgroup = "800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004";
+    // Not the x-coordinate of any point on the curve.
+    let not_on_curve = "800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001";
+    let identity = "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+    for bad in [not_in_subgroup, not_on_curve, identity] {
+        let txt = SETUP_TXT.replacen(generator, bad, 1);
+        assert!(matches!(TrustedSetup::from_txt(&txt), Err(TrustedSetupError::InvalidPoint(_))));
+
+        let json = SETUP_JSON.replacen(generator, bad, 1);
+        assert!(matches!(TrustedSetup::from_json(&json), Err(TrustedSetupError::InvalidPoint(_))));
+    }
+
+    let truncated: String = SETUP_TXT.lines().take(10).collect::<Vec<_>>().join("\n");
+    assert!(matches!(TrustedSetup::from_txt(&truncated), Err(TrustedSetupError::Format(_))));
+
+    let short = SETUP_TXT.replacen(generator, &generator[2..], 1);
+    assert!(matches!(TrustedSetup::from_txt(&short), Err(TrustedSetupError::Format(_))));
+
+    assert!(matches!(TrustedSetup::from_json("{}"), Err(TrustedSetupError::Format(_))));
+    assert!(matches!(TrustedSetup::load("does/not/exist.txt"), Err(TrustedSetupError::Io(_))));
+}
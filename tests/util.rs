@@ -1,7 +1,10 @@
+// Shared between the integration tests, not all of which use every helper.
+#![allow(dead_code)]
+
 use ark_bls12_381::Fr;
 use ark_ff::UniformRand;
 use ark_poly::{DenseUVPolynomial, univariate::DensePolynomial};
-use ark_std::rand::Rng;
+use ark_std::rand::{Rng, SeedableRng, rngs::StdRng};
 
 pub fn poly_generator(poly_count: usize, poly_deg: usize, rng: &mut impl Rng) -> Vec<DensePolynomial<Fr>> {
     let mut poly = vec![];
@@ -17,4 +20,15 @@ pub fn point_generator(point_count: usize, rng: &mut impl Rng) -> Vec<Fr> {
         z.push(Fr::rand(rng));
     }
     z
-}
\ No newline at end of file
+}
+
+/// Deterministic RNG for tests, seeded identically to `ark_std::test_rng`.
+/// Unlike `ark_std::test_rng` its concrete type is exposed, so it can be passed
+/// wherever a `CryptoRng` is required.
+pub fn test_rng() -> StdRng {
+    let seed = [
+        1, 0, 0, 0, 23, 0, 0, 0, 200, 1, 0, 0, 210, 30, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0,
+    ];
+    StdRng::from_seed(seed)
+}